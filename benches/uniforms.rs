@@ -0,0 +1,123 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use gloam::{
+    context::GLContext,
+    headless::{Headless, HeadlessConfig},
+    mesh::Mesh,
+    object::{GLObjectRegistry, Handle},
+    shader::{program::Linker, Program},
+    uniform::Uniform,
+    vertex::{Primitive, Usage, VOBuilder},
+};
+use nalgebra_glm as glm;
+use std::ffi::CString;
+
+const NUM_UNIFORMS: usize = 16;
+
+const VERTEX_SHADER: &str = r#"
+#version 330 core
+in vec3 aPosition;
+uniform mat4 model;
+uniform mat4 view;
+uniform mat4 projection;
+void main() {
+    gl_Position = projection * view * model * vec4(aPosition, 1.0);
+}
+"#;
+
+fn fragment_shader() -> String {
+    let decls = (0..NUM_UNIFORMS)
+        .map(|i| format!("uniform vec4 color{i};\n"))
+        .collect::<String>();
+    let sum = (0..NUM_UNIFORMS)
+        .map(|i| format!("color{i}"))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    format!("#version 330 core\n{decls}out vec4 fragColor;\nvoid main() {{ fragColor = {sum}; }}\n")
+}
+
+fn uniforms() -> Vec<Uniform> {
+    let mut uniforms = vec![
+        Uniform::default_mat4fv("model", false),
+        Uniform::default_mat4fv("view", false),
+        Uniform::default_mat4fv("projection", false),
+    ];
+    uniforms.extend((0..NUM_UNIFORMS).map(|i| Uniform::default_4f(&format!("color{i}"))));
+    uniforms
+}
+
+fn setup() -> (Headless, GLContext, Handle<Program>, Mesh) {
+    let headless = Headless::new(HeadlessConfig {
+        width: 1,
+        height: 1,
+        ..Default::default()
+    })
+    .unwrap();
+    let mut ctx = GLContext::new(GLObjectRegistry::default()).unwrap();
+    let program =
+        Linker::link_sources(&mut ctx, "bench", VERTEX_SHADER, &fragment_shader()).unwrap();
+    let vertex_object = VOBuilder::new(Primitive::Triangles, Usage::Static)
+        .attribute("aPosition", 3, &[0.0; 9])
+        .unwrap()
+        .build(&mut ctx, program)
+        .unwrap();
+    let mesh = Mesh::new(&ctx, vertex_object, program, uniforms()).unwrap();
+    (headless, ctx, program, mesh)
+}
+
+/// Location lookups: what every upload used to cost versus the per-program cache.
+fn uniform_locations(c: &mut Criterion) {
+    let (_headless, mut ctx, program, _mesh) = setup();
+    ctx.try_use_program(program).unwrap();
+    let gl_program = ctx.get_program(program).unwrap().gl_object_id;
+    let uniforms = uniforms();
+
+    let mut group = c.benchmark_group("uniform_upload");
+    group.bench_function("uncached_location", |b| {
+        b.iter(|| {
+            for uniform in &uniforms {
+                let name = CString::new(uniform.name.as_str()).unwrap();
+                let loc = unsafe { gl::GetUniformLocation(gl_program, name.as_ptr()) };
+                assert_ne!(loc, -1);
+            }
+        })
+    });
+    group.bench_function("cached_location", |b| {
+        b.iter(|| {
+            for uniform in &uniforms {
+                ctx.try_get_uniform_loc(&uniform.name).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Per-frame uniform uploads of a mesh where only the model matrix changes every frame.
+fn mesh_uniforms(c: &mut Criterion) {
+    let (_headless, mut ctx, program, mut mesh) = setup();
+    ctx.try_use_program(program).unwrap();
+    let mut angle = 0.0_f32;
+
+    let mut group = c.benchmark_group("mesh_uniforms");
+    group.bench_function("all", |b| {
+        b.iter(|| {
+            angle += 0.01;
+            let model = glm::rotate_y(&glm::Mat4::identity(), angle);
+            mesh.set_uniform_on_cpu(Uniform::new_mat4fv("model", model, false))
+                .unwrap();
+            mesh.try_set_uniforms_on_gpu(&ctx).unwrap();
+        })
+    });
+    group.bench_function("changed_only", |b| {
+        b.iter(|| {
+            angle += 0.01;
+            let model = glm::rotate_y(&glm::Mat4::identity(), angle);
+            mesh.set_uniform_on_cpu(Uniform::new_mat4fv("model", model, false))
+                .unwrap();
+            mesh.try_set_changed_uniforms_on_gpu(&ctx).unwrap();
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, uniform_locations, mesh_uniforms);
+criterion_main!(benches);
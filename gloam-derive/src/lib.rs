@@ -0,0 +1,191 @@
+//! Derive macros for [gloam](https://github.com/solidiquis/gloam). Use them through the
+//! re-exports in `gloam` rather than depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `gloam::uniform::Uniforms` for a struct with named fields, producing one uniform
+/// per field. Every field type must implement `gloam::uniform::AsUniformType`.
+///
+/// Fields accept the following options through `#[uniform(...)]`:
+/// - `name = "..."`: name of the uniform in the shader; defaults to the name of the field.
+/// - `transpose`: transpose matrices when sending them to the GPU.
+/// - `skip`: don't treat the field as a uniform.
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniforms_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `gloam::vertex::Vertex` for a `#[repr(C)]` struct with named fields, producing one
+/// vertex attribute per field. Every field type must implement `gloam::vertex::AttributeType`.
+///
+/// Fields accept the following options through `#[vertex(...)]`:
+/// - `name = "..."`: name of the attribute in the shader; defaults to the name of the field.
+/// - `normalized`: map integer values to `[0, 1]`, or `[-1, 1]` if signed.
+/// - `integer`: pass integer values to integer shader inputs without converting them.
+/// - `skip`: don't treat the field as an attribute e.g. for padding.
+#[proc_macro_derive(Vertex, attributes(vertex))]
+pub fn derive_vertex(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    vertex_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct FieldOptions {
+    name: Option<String>,
+    transpose: bool,
+    normalized: bool,
+    integer: bool,
+    skip: bool,
+}
+
+fn uniforms_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = named_fields(input, "Uniforms")?;
+
+    let mut uniforms = Vec::with_capacity(fields.len());
+    for field in fields {
+        let options = parse_field_options(field, "uniform")?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let name = options.name.unwrap_or_else(|| ident.to_string());
+        let transpose = options.transpose;
+        uniforms.push(quote! {
+            ::gloam::uniform::Uniform::new(
+                #name,
+                ::gloam::uniform::AsUniformType::as_uniform_type(&self.#ident),
+                #transpose,
+            )
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::gloam::uniform::Uniforms for #ident #ty_generics #where_clause {
+            fn uniforms(&self) -> ::std::vec::Vec<::gloam::uniform::Uniform> {
+                ::std::vec![#(#uniforms),*]
+            }
+        }
+    })
+}
+
+fn vertex_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if !is_repr_c(input)? {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Vertex can only be derived for #[repr(C)] structs",
+        ));
+    }
+    let fields = named_fields(input, "Vertex")?;
+
+    let mut attributes = Vec::with_capacity(fields.len());
+    for field in fields {
+        let options = parse_field_options(field, "vertex")?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let name = options.name.unwrap_or_else(|| ident.to_string());
+        let format = match (options.normalized, options.integer) {
+            (true, true) => {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "attribute can't be both normalized and integer",
+                ))
+            }
+            (true, false) => quote!(Normalized),
+            (false, true) => quote!(Integer),
+            (false, false) => quote!(Float),
+        };
+        attributes.push(quote! {
+            ::gloam::vertex::AttributeLayout {
+                name: ::std::string::String::from(#name),
+                components: <#ty as ::gloam::vertex::AttributeType>::COMPONENTS,
+                gl_type: <#ty as ::gloam::vertex::AttributeType>::GL_TYPE,
+                format: ::gloam::vertex::AttributeFormat::#format,
+                offset: ::std::mem::offset_of!(Self, #ident),
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        unsafe impl #impl_generics ::gloam::vertex::Vertex for #ident #ty_generics #where_clause {
+            fn attributes() -> ::std::vec::Vec<::gloam::vertex::AttributeLayout> {
+                ::std::vec![#(#attributes),*]
+            }
+        }
+    })
+}
+
+fn named_fields<'a>(
+    input: &'a DeriveInput,
+    derive: &str,
+) -> syn::Result<impl ExactSizeIterator<Item = &'a syn::Field>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            format!("{derive} can only be derived for structs"),
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            format!("{derive} can only be derived for structs with named fields"),
+        ));
+    };
+    Ok(fields.named.iter())
+}
+
+fn is_repr_c(input: &DeriveInput) -> syn::Result<bool> {
+    let mut repr_c = false;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            repr_c |= meta.path.is_ident("C");
+            // Skip arguments such as `align(16)`.
+            if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr_c)
+}
+
+/// Parses the options given through the `#[<helper>(...)]` attributes of `field`.
+fn parse_field_options(field: &syn::Field, helper: &str) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident(helper)) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if helper == "uniform" && meta.path.is_ident("transpose") {
+                options.transpose = true;
+            } else if helper == "vertex" && meta.path.is_ident("normalized") {
+                options.normalized = true;
+            } else if helper == "vertex" && meta.path.is_ident("integer") {
+                options.integer = true;
+            } else if helper == "uniform" {
+                return Err(meta.error("expected `name`, `transpose` or `skip`"));
+            } else {
+                return Err(meta.error("expected `name`, `normalized`, `integer` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
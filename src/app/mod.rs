@@ -5,6 +5,9 @@ use crate::{
     window::{Window, WindowConfig},
 };
 
+#[cfg(feature = "headless")]
+use crate::headless::{Headless, HeadlessConfig};
+
 pub fn init_default_opengl_3_3(title: &str) -> Result<(Window, GLContext)> {
     let window_config = WindowConfig {
         title,
@@ -15,3 +18,18 @@ pub fn init_default_opengl_3_3(title: &str) -> Result<(Window, GLContext)> {
     let ctx = GLContext::new(object_registry)?;
     Ok((window, ctx))
 }
+
+/// Like [init_default_opengl_3_3] but without a window; rendering goes to an offscreen surface
+/// of the given dimensions.
+#[cfg(feature = "headless")]
+pub fn init_headless_opengl_3_3(width: u32, height: u32) -> Result<(Headless, GLContext)> {
+    let headless_config = HeadlessConfig {
+        width,
+        height,
+        ..Default::default()
+    };
+    let headless = Headless::new(headless_config)?;
+    let object_registry = GLObjectRegistry::default();
+    let ctx = GLContext::new(object_registry)?;
+    Ok((headless, ctx))
+}
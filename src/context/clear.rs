@@ -1,18 +1,24 @@
 use super::GLContext;
-use gl::types::{GLbitfield, GLfloat};
+use gl::types::{GLbitfield, GLdouble, GLfloat, GLint};
 
+/// Buffers to clear along with the value to clear them to. Clears respect the color, depth and
+/// stencil write masks as well as the scissor test.
 #[derive(Debug, Copy, Clone)]
 pub enum ClearMask {
     // RGBA
     Color(GLfloat, GLfloat, GLfloat, GLfloat),
+    /// Clears the depth buffer to whatever clear depth was last set, `1.0` by default.
     DepthBuffer,
+    Depth(GLdouble),
+    Stencil(GLint),
 }
 
 impl From<ClearMask> for GLbitfield {
     fn from(value: ClearMask) -> Self {
         match value {
             ClearMask::Color(_, _, _, _) => gl::COLOR_BUFFER_BIT,
-            ClearMask::DepthBuffer => gl::DEPTH_BUFFER_BIT,
+            ClearMask::DepthBuffer | ClearMask::Depth(_) => gl::DEPTH_BUFFER_BIT,
+            ClearMask::Stencil(_) => gl::STENCIL_BUFFER_BIT,
         }
     }
 }
@@ -30,7 +36,13 @@ impl GLContext {
                 ClearMask::Color(r, g, b, a) => unsafe {
                     gl::ClearColor(r, g, b, a);
                 },
-                _ => continue,
+                ClearMask::Depth(depth) => unsafe {
+                    gl::ClearDepth(depth);
+                },
+                ClearMask::Stencil(stencil) => unsafe {
+                    gl::ClearStencil(stencil);
+                },
+                ClearMask::DepthBuffer => continue,
             }
         }
         if let Some(bitmask) = bitmasks.into_iter().reduce(|a, b| a | b) {
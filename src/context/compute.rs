@@ -0,0 +1,91 @@
+use super::GLContext;
+use crate::{
+    error::{gl_check_err, Error, Result},
+    object::Handle,
+    shader::{program::Program, ShaderType},
+};
+use gl::types::{GLbitfield, GLuint};
+
+/// Kinds of memory accesses that should observe writes made by shaders before the barrier, i.e.
+/// `glMemoryBarrier`. For example, use [MemoryBarrier::VertexAttribArray] when a compute shader
+/// writes to a buffer that's subsequently used as a vertex buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryBarrier {
+    VertexAttribArray,
+    ElementArray,
+    Uniform,
+    TextureFetch,
+    ShaderImageAccess,
+    Command,
+    PixelBuffer,
+    TextureUpdate,
+    BufferUpdate,
+    Framebuffer,
+    TransformFeedback,
+    AtomicCounter,
+    ShaderStorage,
+    All,
+}
+
+impl From<MemoryBarrier> for GLbitfield {
+    fn from(value: MemoryBarrier) -> Self {
+        match value {
+            MemoryBarrier::VertexAttribArray => gl::VERTEX_ATTRIB_ARRAY_BARRIER_BIT,
+            MemoryBarrier::ElementArray => gl::ELEMENT_ARRAY_BARRIER_BIT,
+            MemoryBarrier::Uniform => gl::UNIFORM_BARRIER_BIT,
+            MemoryBarrier::TextureFetch => gl::TEXTURE_FETCH_BARRIER_BIT,
+            MemoryBarrier::ShaderImageAccess => gl::SHADER_IMAGE_ACCESS_BARRIER_BIT,
+            MemoryBarrier::Command => gl::COMMAND_BARRIER_BIT,
+            MemoryBarrier::PixelBuffer => gl::PIXEL_BUFFER_BARRIER_BIT,
+            MemoryBarrier::TextureUpdate => gl::TEXTURE_UPDATE_BARRIER_BIT,
+            MemoryBarrier::BufferUpdate => gl::BUFFER_UPDATE_BARRIER_BIT,
+            MemoryBarrier::Framebuffer => gl::FRAMEBUFFER_BARRIER_BIT,
+            MemoryBarrier::TransformFeedback => gl::TRANSFORM_FEEDBACK_BARRIER_BIT,
+            MemoryBarrier::AtomicCounter => gl::ATOMIC_COUNTER_BARRIER_BIT,
+            MemoryBarrier::ShaderStorage => gl::SHADER_STORAGE_BARRIER_BIT,
+            MemoryBarrier::All => gl::ALL_BARRIER_BITS,
+        }
+    }
+}
+
+impl GLContext {
+    /// Uses `program`, which must be a compute program, and launches `x * y * z` work groups. Like
+    /// [GLContext::try_use_program], this fails if another program is in use. Use
+    /// [GLContext::memory_barrier] before consuming what the compute shader wrote. Requires an
+    /// OpenGL 4.3 context.
+    pub fn dispatch_compute(
+        &mut self,
+        program: Handle<Program>,
+        x: GLuint,
+        y: GLuint,
+        z: GLuint,
+    ) -> Result<()> {
+        let (required, current) = (ShaderType::Compute.min_gl_version(), self.gl_version());
+        if current < required {
+            return Err(Error::UnsupportedGLVersion {
+                feature: "compute dispatch",
+                required,
+                current,
+            });
+        }
+        if !self.get_program(program)?.is_compute() {
+            return Err(Error::InvalidProgramStages(
+                "can't dispatch a program without a compute shader".to_string(),
+            ));
+        }
+        self.try_use_program(program)?;
+        unsafe { gl::DispatchCompute(x, y, z) };
+        gl_check_err()
+    }
+
+    pub fn memory_barrier(&self, barriers: &[MemoryBarrier]) {
+        let bitmask = barriers
+            .iter()
+            .map(|barrier| GLbitfield::from(*barrier))
+            .reduce(|a, b| a | b);
+
+        if let Some(bitmask) = bitmask {
+            unsafe { gl::MemoryBarrier(bitmask) }
+        }
+    }
+}
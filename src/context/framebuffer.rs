@@ -0,0 +1,46 @@
+use super::GLContext;
+use crate::{
+    error::{Error, Result},
+    framebuffer::Framebuffer,
+    object::Handle,
+};
+
+impl GLContext {
+    /// Will return an error if a different framebuffer is currently bound; unbind it before
+    /// binding a new one. The viewport is left as is, so it may need to be adjusted to the
+    /// dimensions of the framebuffer.
+    pub fn try_bind_framebuffer(&mut self, fb_desc: Handle<Framebuffer>) -> Result<()> {
+        if self.bound_framebuffer.is_some_and(|fb| fb == fb_desc) {
+            return Ok(());
+        } else if self.bound_framebuffer.is_some() {
+            return Err(Error::AnotherFramebufferBound);
+        }
+        let framebuffer = self.get_framebuffer(fb_desc)?;
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, framebuffer.gl_object_id);
+        }
+        log::debug!(
+            "currently bound framebuffer: object_storage_id={fb_desc:?} -> {framebuffer:?}"
+        );
+        self.bound_framebuffer = Some(fb_desc);
+        Ok(())
+    }
+
+    /// Unbinds the current framebuffer, making the default framebuffer the render target again.
+    pub fn unbind_current_framebuffer(&mut self) -> Option<Handle<Framebuffer>> {
+        let obj_desc = self.bound_framebuffer.take()?;
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+        log::debug!("framebuffer unbound: object_storage_id={obj_desc:?}");
+        Some(obj_desc)
+    }
+
+    pub fn framebuffer_bound(&self, fb_desc: Handle<Framebuffer>) -> bool {
+        self.bound_framebuffer.is_some_and(|od| od == fb_desc)
+    }
+
+    pub fn get_current_bound_framebuffer(&self) -> Option<Handle<Framebuffer>> {
+        self.bound_framebuffer
+    }
+}
@@ -0,0 +1,54 @@
+use super::GLContext;
+use crate::{
+    framebuffer::Framebuffer,
+    object::{GLObjectDescriptor, Handle},
+    shader::program::Program,
+    texture::Texture,
+    uniform::UniformBuffer,
+    vertex::VertexObject,
+};
+
+impl GLContext {
+    /// Deletes every object whose [OwnedHandle]s have all been dropped since the last call. If a
+    /// collected object is currently bound or in use it is unbound first. Returns the number of
+    /// objects that were deleted.
+    ///
+    /// [OwnedHandle]: crate::object::OwnedHandle
+    pub fn collect_garbage(&mut self) -> usize {
+        let garbage = self.take_garbage();
+        let mut collected = 0;
+
+        for desc in garbage {
+            self.release(desc);
+            if self.remove_object(desc).is_some() {
+                collected += 1;
+            }
+        }
+        if collected > 0 {
+            log::debug!("collected {collected} objects");
+        }
+        collected
+    }
+
+    /// Resets any context state that refers to the object described by `desc`. At most one branch
+    /// matches, since a descriptor only converts to the handle type of its own kind.
+    fn release(&mut self, desc: GLObjectDescriptor) {
+        if let Ok(handle) = Handle::<Program>::try_from(desc) {
+            if self.active_program.is_some_and(|p| p == handle) {
+                self.detach_current_program();
+            }
+        } else if let Ok(handle) = Handle::<VertexObject>::try_from(desc) {
+            if self.bound_vertex_object.is_some_and(|vo| vo == handle) {
+                self.unbind_current_vertex_object();
+            }
+        } else if let Ok(handle) = Handle::<Texture>::try_from(desc) {
+            self.deactivate_texture(handle);
+        } else if let Ok(handle) = Handle::<Framebuffer>::try_from(desc) {
+            if self.framebuffer_bound(handle) {
+                self.unbind_current_framebuffer();
+            }
+        } else if let Ok(handle) = Handle::<UniformBuffer>::try_from(desc) {
+            self.unbind_uniform_buffer_everywhere(handle);
+        }
+    }
+}
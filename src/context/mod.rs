@@ -1,42 +1,65 @@
 use crate::{
-    error::{gl_check_err, Result},
-    object::{GLObjectDescriptor, GLObjectRegistry},
+    error::Result,
+    framebuffer::Framebuffer,
+    internal_utils::gl_version,
+    object::{GLObjectRegistry, Handle},
+    shader::program::Program,
+    state::RenderState,
+    texture::Texture,
+    uniform::UniformBuffer,
+    vertex::VertexObject,
 };
-use gl::types::{GLenum, GLint, GLsizei};
+use gl::types::{GLint, GLsizei};
 use std::ops::{Deref, DerefMut};
 
 pub mod clear;
 pub use clear::ClearMask;
 
+pub mod compute;
+pub use compute::MemoryBarrier;
+
+pub mod framebuffer;
+pub mod garbage;
 pub mod program;
+
+pub mod readback;
+pub use readback::DepthImage;
+
+pub mod state;
+
 pub mod texture;
+pub mod uniform_buffer;
 pub mod vertex;
 
 #[derive(Debug)]
 pub struct GLContext {
     object_registry: GLObjectRegistry,
-    active_textures: Vec<Option<GLObjectDescriptor>>,
-    bound_vertex_object: Option<GLObjectDescriptor>,
-    active_program: Option<GLObjectDescriptor>,
+    active_textures: Vec<Option<Handle<Texture>>>,
+    bound_vertex_object: Option<Handle<VertexObject>>,
+    active_program: Option<Handle<Program>>,
+    bound_framebuffer: Option<Handle<Framebuffer>>,
+    uniform_buffer_bindings: Vec<Option<Handle<UniformBuffer>>>,
+    render_state: RenderState,
+    gl_version: (GLint, GLint),
 }
 
 impl GLContext {
     pub fn new(object_registry: GLObjectRegistry) -> Result<Self> {
         Ok(Self {
             active_textures: Self::init_texture_units()?,
+            uniform_buffer_bindings: Self::init_uniform_buffer_bindings()?,
             object_registry,
             bound_vertex_object: None,
             active_program: None,
+            bound_framebuffer: None,
+            render_state: RenderState::default(),
+            gl_version: gl_version(),
         })
     }
 
-    pub fn try_enable_depth_test(&self, depth_func: Option<GLenum>) -> Result<()> {
-        unsafe {
-            gl::Enable(gl::DEPTH_TEST);
-            let func = depth_func.unwrap_or(gl::LESS);
-            gl::DepthFunc(func);
-        }
-        gl_check_err()
+    /// Version of the OpenGL context as `(major, minor)`.
+    pub fn gl_version(&self) -> (GLint, GLint) {
+        self.gl_version
     }
 
     pub fn viewport(&self, x: GLint, y: GLint, width: GLsizei, height: GLsizei) {
@@ -1,8 +1,9 @@
 use super::GLContext;
 use crate::{
     error::{Error, Result},
-    internal_utils::as_gl_bool,
-    object::GLObjectDescriptor,
+    internal_utils::{as_gl_bool, try_into},
+    object::Handle,
+    shader::program::Program,
     uniform::{Uniform, UniformType},
 };
 use nalgebra_glm as glm;
@@ -11,7 +12,7 @@ use std::ffi::CString;
 impl GLContext {
     /// Will return an error if a different program is currently active; if that's the case, it
     /// should be detached before using another program.
-    pub fn try_use_program(&mut self, prog_desc: GLObjectDescriptor) -> Result<()> {
+    pub fn try_use_program(&mut self, prog_desc: Handle<Program>) -> Result<()> {
         // Program is already in use
         if self.active_program.is_some_and(|desc| desc == prog_desc) {
             return Ok(());
@@ -27,8 +28,53 @@ impl GLContext {
         Ok(())
     }
 
+    /// Recompiles and relinks every program whose shader files, or files they `#include`, were
+    /// modified since it was linked. The new program takes the place of the old one in the
+    /// registry so existing handles, and the [Mesh](crate::mesh::Mesh)es holding them, keep
+    /// working; if the old program was in use, the new one is used in its place. Uniforms have to
+    /// be set again but uniform blocks keep their binding points. If recompiling fails the old
+    /// program stays in place, the error is logged, and the program isn't retried until its files
+    /// change again. Returns the reloaded programs.
+    pub fn reload_modified_programs(&mut self) -> Vec<Handle<Program>> {
+        let modified = self
+            .iter::<Program>()
+            .filter(|(_, program)| program.is_modified())
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+
+        let mut reloaded = Vec::with_capacity(modified.len());
+        for handle in modified {
+            let relinked = self.get_program(handle).and_then(Program::relink);
+            let result = relinked.and_then(|program| self.replace(handle, program));
+
+            match result {
+                Ok(mut old) => {
+                    let program = self.get_mut(handle).unwrap();
+                    for (block, binding) in old.take_block_bindings() {
+                        if let Err(e) = program.bind_uniform_block(&block, binding) {
+                            log::warn!("failed to rebind uniform block of {handle:?}: {e}");
+                        }
+                    }
+                    let program = self.get_program(handle).unwrap();
+                    if self.active_program.is_some_and(|desc| desc == handle) {
+                        unsafe { gl::UseProgram(program.gl_object_id) };
+                    }
+                    log::info!("reloaded program: object_storage_id={handle:?} -> {program:?}");
+                    reloaded.push(handle);
+                }
+                Err(e) => {
+                    log::error!("failed to reload program {handle:?}: {e}");
+                    if let Ok(program) = self.get_mut(handle) {
+                        program.refresh_modified_times();
+                    }
+                }
+            }
+        }
+        reloaded
+    }
+
     /// Detach the program that's currently in use.
-    pub fn detach_current_program(&mut self) -> Option<GLObjectDescriptor> {
+    pub fn detach_current_program(&mut self) -> Option<Handle<Program>> {
         let active_program_desc = self.active_program.take()?;
         unsafe {
             gl::UseProgram(0);
@@ -39,7 +85,7 @@ impl GLContext {
 
     pub fn try_get_attrib_loc(
         &self,
-        prog_desc: GLObjectDescriptor,
+        prog_desc: Handle<Program>,
         attrib: &str,
     ) -> Result<gl::types::GLuint> {
         self.ensure_program_active()?;
@@ -54,27 +100,36 @@ impl GLContext {
         })
     }
 
+    /// Locations are cached per program so only the first lookup of each name queries the driver.
     pub fn try_get_uniform_loc(&self, uniform: &str) -> Result<gl::types::GLint> {
         let prog_desc = self.try_get_active_program()?;
-        let c_uniform = CString::new(uniform).map_err(Error::boxed)?;
-
-        self.get_program(prog_desc).and_then(|prog| unsafe {
-            let loc = gl::GetUniformLocation(prog.gl_object_id, c_uniform.as_ptr());
-            if loc == -1 {
-                return Err(Error::UniformLocNotFound(uniform.into()));
-            }
-            Ok(loc)
-        })
+        let loc = self.get_program(prog_desc)?.uniform_location(uniform)?;
+        if loc == -1 {
+            return Err(Error::UniformLocNotFound(uniform.into()));
+        }
+        Ok(loc)
     }
 
+    /// Sets `uniform` on the active program.
     pub fn try_set_uniform(&self, uniform: &Uniform) -> Result<()> {
+        self.try_set_uniform_impl(uniform)?;
+        // Whichever mesh uploaded its uniforms last no longer has all of its values on the GPU.
+        if let Ok(program) = self.get_program(self.try_get_active_program()?) {
+            program.set_last_uploader(None);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn try_set_uniform_impl(&self, uniform: &Uniform) -> Result<()> {
         self.ensure_program_active()?;
         let transpose = as_gl_bool(uniform.transpose);
         self.try_get_uniform_loc(&uniform.name).map(|loc| unsafe {
-            match uniform.kind {
-                UniformType::D(v) => gl::Uniform1d(loc, v),
-                UniformType::F(v) => gl::Uniform1f(loc, v),
-                UniformType::I(v) => gl::Uniform1i(loc, v),
+            // Vectors and matrices are stored as contiguous arrays of their components so slices
+            // of them can be passed to OpenGL as is.
+            match &uniform.kind {
+                UniformType::D(v) => gl::Uniform1d(loc, *v),
+                UniformType::F(v) => gl::Uniform1f(loc, *v),
+                UniformType::I(v) => gl::Uniform1i(loc, *v),
                 UniformType::D2(v) => gl::Uniform2d(loc, v.x, v.y),
                 UniformType::F2(v) => gl::Uniform2f(loc, v.x, v.y),
                 UniformType::I2(v) => gl::Uniform2i(loc, v.x, v.y),
@@ -85,28 +140,82 @@ impl GLContext {
                 UniformType::F4(v) => gl::Uniform4f(loc, v.x, v.y, v.z, v.w),
                 UniformType::I4(v) => gl::Uniform4i(loc, v.x, v.y, v.z, v.w),
                 UniformType::DMat2(v) => {
-                    gl::UniformMatrix2dv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix2dv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
                 }
                 UniformType::FMat2(v) => {
-                    gl::UniformMatrix2fv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix2fv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
                 }
                 UniformType::DMat3(v) => {
-                    gl::UniformMatrix3dv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix3dv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
                 }
                 UniformType::FMat3(v) => {
-                    gl::UniformMatrix3fv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix3fv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
                 }
                 UniformType::DMat4(v) => {
-                    gl::UniformMatrix4dv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix4dv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
                 }
                 UniformType::FMat4(v) => {
-                    gl::UniformMatrix4fv(loc, 1, transpose, glm::value_ptr(&v).as_ptr())
+                    gl::UniformMatrix4fv(loc, 1, transpose, glm::value_ptr(v).as_ptr())
+                }
+                UniformType::DArray(v) => {
+                    gl::Uniform1dv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::FArray(v) => {
+                    gl::Uniform1fv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::IArray(v) => {
+                    gl::Uniform1iv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::D2Array(v) => {
+                    gl::Uniform2dv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::F2Array(v) => {
+                    gl::Uniform2fv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::I2Array(v) => {
+                    gl::Uniform2iv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::D3Array(v) => {
+                    gl::Uniform3dv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::F3Array(v) => {
+                    gl::Uniform3fv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::I3Array(v) => {
+                    gl::Uniform3iv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::D4Array(v) => {
+                    gl::Uniform4dv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::F4Array(v) => {
+                    gl::Uniform4fv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::I4Array(v) => {
+                    gl::Uniform4iv(loc, try_into!(v.len()), v.as_ptr().cast())
+                }
+                UniformType::DMat2Array(v) => {
+                    gl::UniformMatrix2dv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
+                }
+                UniformType::FMat2Array(v) => {
+                    gl::UniformMatrix2fv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
+                }
+                UniformType::DMat3Array(v) => {
+                    gl::UniformMatrix3dv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
+                }
+                UniformType::FMat3Array(v) => {
+                    gl::UniformMatrix3fv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
+                }
+                UniformType::DMat4Array(v) => {
+                    gl::UniformMatrix4dv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
+                }
+                UniformType::FMat4Array(v) => {
+                    gl::UniformMatrix4fv(loc, try_into!(v.len()), transpose, v.as_ptr().cast())
                 }
             }
         })
     }
 
-    pub fn try_get_active_program(&self) -> Result<GLObjectDescriptor> {
+    pub fn try_get_active_program(&self) -> Result<Handle<Program>> {
         self.active_program.ok_or(Error::NoActiveProgram)
     }
 
@@ -0,0 +1,100 @@
+use super::GLContext;
+use crate::{
+    error::{gl_check_err, misc_error, Error, Result},
+    internal_utils::try_into,
+};
+use gl::types::{GLenum, GLint, GLsizei};
+use image::{imageops, ImageBuffer, Luma, Pixel, RgbaImage};
+use std::{ffi::c_void, path::Path};
+
+/// Single-channel image of depth values in the range `[0.0, 1.0]`.
+pub type DepthImage = ImageBuffer<Luma<f32>, Vec<f32>>;
+
+impl GLContext {
+    /// Reads a rectangle of the color buffer of whichever framebuffer is currently bound; this is
+    /// the default framebuffer unless [GLContext::try_bind_framebuffer] was used. `x` and `y` are
+    /// the lower-left corner of the rectangle in window coordinates. OpenGL's origin is the
+    /// bottom-left so rows are flipped to get a top-left origin image, which is the reverse of
+    /// what happens when a texture is loaded with [TextureBuilder::new_2d_rgba8].
+    ///
+    /// [TextureBuilder::new_2d_rgba8]: crate::texture::TextureBuilder::new_2d_rgba8
+    pub fn try_read_pixels(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+    ) -> Result<RgbaImage> {
+        let mut image: RgbaImage =
+            Self::read_pixels_impl(x, y, width, height, gl::RGBA, gl::UNSIGNED_BYTE)?;
+        imageops::flip_vertical_in_place(&mut image);
+        Ok(image)
+    }
+
+    /// Reads a rectangle of the depth buffer of the currently bound framebuffer. See
+    /// [GLContext::try_read_pixels].
+    pub fn try_read_depth_pixels(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+    ) -> Result<DepthImage> {
+        let mut image: DepthImage =
+            Self::read_pixels_impl(x, y, width, height, gl::DEPTH_COMPONENT, gl::FLOAT)?;
+        imageops::flip_vertical_in_place(&mut image);
+        Ok(image)
+    }
+
+    /// Same as [GLContext::try_read_pixels] but writes the image to `path`. The image format is
+    /// derived from the file extension.
+    pub fn try_save_pixels<P: AsRef<Path>>(
+        &self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        path: P,
+    ) -> Result<()> {
+        let image = self.try_read_pixels(x, y, width, height)?;
+        image
+            .save(path.as_ref())
+            .map_err(|e| misc_error!("failed to save pixels: {e}"))
+    }
+
+    fn read_pixels_impl<P: Pixel>(
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        kind: GLenum,
+    ) -> Result<ImageBuffer<P, Vec<P::Subpixel>>>
+    where
+        P::Subpixel: Default,
+    {
+        let (img_width, img_height): (u32, u32) = (try_into!(width), try_into!(height));
+        let len = img_width as usize * img_height as usize * P::CHANNEL_COUNT as usize;
+        let mut data = vec![P::Subpixel::default(); len];
+
+        unsafe {
+            let mut previous_alignment = 0;
+            gl::GetIntegerv(gl::PACK_ALIGNMENT, &mut previous_alignment);
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+            gl::ReadPixels(
+                x,
+                y,
+                width,
+                height,
+                format,
+                kind,
+                data.as_mut_ptr() as *mut c_void,
+            );
+            gl::PixelStorei(gl::PACK_ALIGNMENT, previous_alignment);
+        }
+        gl_check_err()?;
+
+        ImageBuffer::from_raw(img_width, img_height, data)
+            .ok_or(Error::Misc("pixel buffer has unexpected size".to_string()))
+    }
+}
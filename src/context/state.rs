@@ -0,0 +1,205 @@
+use super::GLContext;
+use crate::{
+    error::{gl_check_err, Result},
+    internal_utils::as_gl_bool,
+    state::{CompareFunc, RenderState, Scissor, StencilState},
+};
+use gl::types::{GLenum, GLint, GLsizei};
+
+impl GLContext {
+    /// Applies `state`, only issuing OpenGL calls for the parts that differ from the state that
+    /// was last applied through this [GLContext]. Changing fixed-function state directly through
+    /// `gl` will go unnoticed by the cache.
+    pub fn apply_render_state(&mut self, state: &RenderState) -> Result<()> {
+        let current = self.render_state;
+        unsafe {
+            if state.blend != current.blend {
+                match state.blend {
+                    Some(blend) => {
+                        if current.blend.is_none() {
+                            gl::Enable(gl::BLEND);
+                        }
+                        gl::BlendEquationSeparate(
+                            blend.color_equation.into(),
+                            blend.alpha_equation.into(),
+                        );
+                        gl::BlendFuncSeparate(
+                            blend.src_color.into(),
+                            blend.dst_color.into(),
+                            blend.src_alpha.into(),
+                            blend.dst_alpha.into(),
+                        );
+                    }
+                    None => gl::Disable(gl::BLEND),
+                }
+            }
+
+            if state.cull_face != current.cull_face {
+                match state.cull_face {
+                    Some(face) => {
+                        if current.cull_face.is_none() {
+                            gl::Enable(gl::CULL_FACE);
+                        }
+                        gl::CullFace(face.into());
+                    }
+                    None => gl::Disable(gl::CULL_FACE),
+                }
+            }
+
+            if state.front_face != current.front_face {
+                gl::FrontFace(state.front_face.into());
+            }
+
+            if state.polygon_mode != current.polygon_mode {
+                gl::PolygonMode(gl::FRONT_AND_BACK, state.polygon_mode.into());
+            }
+
+            if state.depth_test != current.depth_test {
+                match state.depth_test {
+                    Some(func) => {
+                        if current.depth_test.is_none() {
+                            gl::Enable(gl::DEPTH_TEST);
+                        }
+                        gl::DepthFunc(func.into());
+                    }
+                    None => gl::Disable(gl::DEPTH_TEST),
+                }
+            }
+
+            if state.depth_write != current.depth_write {
+                gl::DepthMask(as_gl_bool(state.depth_write));
+            }
+
+            if state.depth_range != current.depth_range {
+                let (near, far) = state.depth_range;
+                gl::DepthRange(near, far);
+            }
+
+            if state.polygon_offset != current.polygon_offset {
+                match state.polygon_offset {
+                    Some(offset) => {
+                        if current.polygon_offset.is_none() {
+                            for cap in POLYGON_OFFSET_CAPS {
+                                gl::Enable(cap);
+                            }
+                        }
+                        gl::PolygonOffset(offset.factor, offset.units);
+                    }
+                    None => {
+                        for cap in POLYGON_OFFSET_CAPS {
+                            gl::Disable(cap);
+                        }
+                    }
+                }
+            }
+
+            if state.stencil_test != current.stencil_test {
+                match state.stencil_test {
+                    Some(stencil) => {
+                        if current.stencil_test.is_none() {
+                            gl::Enable(gl::STENCIL_TEST);
+                        }
+                        gl::StencilFunc(stencil.func.into(), stencil.reference, stencil.read_mask);
+                        gl::StencilOp(
+                            stencil.fail.into(),
+                            stencil.depth_fail.into(),
+                            stencil.pass.into(),
+                        );
+                    }
+                    None => gl::Disable(gl::STENCIL_TEST),
+                }
+            }
+
+            if state.stencil_write_mask != current.stencil_write_mask {
+                gl::StencilMask(state.stencil_write_mask);
+            }
+
+            if state.scissor != current.scissor {
+                match state.scissor {
+                    Some(Scissor {
+                        x,
+                        y,
+                        width,
+                        height,
+                    }) => {
+                        if current.scissor.is_none() {
+                            gl::Enable(gl::SCISSOR_TEST);
+                        }
+                        gl::Scissor(x, y, width, height);
+                    }
+                    None => gl::Disable(gl::SCISSOR_TEST),
+                }
+            }
+        }
+        gl_check_err()?;
+        log::debug!("applied render state: {state:?}");
+        self.render_state = *state;
+        Ok(())
+    }
+
+    /// The render state that was last applied.
+    pub fn render_state(&self) -> &RenderState {
+        &self.render_state
+    }
+
+    /// Enables the depth test; `depth_func` defaults to `GL_LESS`.
+    pub fn try_enable_depth_test(&mut self, depth_func: Option<GLenum>) -> Result<()> {
+        let func = depth_func.map_or(Ok(CompareFunc::Less), CompareFunc::try_from)?;
+        let state = RenderState {
+            depth_test: Some(func),
+            ..self.render_state
+        };
+        self.apply_render_state(&state)
+    }
+
+    pub fn disable_depth_test(&mut self) -> Result<()> {
+        let state = RenderState {
+            depth_test: None,
+            ..self.render_state
+        };
+        self.apply_render_state(&state)
+    }
+
+    /// Limits clears and draws to the given rectangle. See [Scissor].
+    pub fn try_enable_scissor_test(
+        &mut self,
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+    ) -> Result<()> {
+        let state = RenderState {
+            scissor: Some(Scissor {
+                x,
+                y,
+                width,
+                height,
+            }),
+            ..self.render_state
+        };
+        self.apply_render_state(&state)
+    }
+
+    pub fn disable_scissor_test(&mut self) -> Result<()> {
+        let state = RenderState {
+            scissor: None,
+            ..self.render_state
+        };
+        self.apply_render_state(&state)
+    }
+
+    /// `None` disables the stencil test. See [StencilState].
+    pub fn try_set_stencil_test(&mut self, stencil: Option<StencilState>) -> Result<()> {
+        let state = RenderState {
+            stencil_test: stencil,
+            ..self.render_state
+        };
+        self.apply_render_state(&state)
+    }
+}
+
+const POLYGON_OFFSET_CAPS: [GLenum; 3] = [
+    gl::POLYGON_OFFSET_FILL,
+    gl::POLYGON_OFFSET_LINE,
+    gl::POLYGON_OFFSET_POINT,
+];
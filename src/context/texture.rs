@@ -2,15 +2,15 @@ use super::GLContext;
 use crate::{
     error::{gl_check_err, Error, Result},
     internal_utils::try_into,
-    object::GLObjectDescriptor,
-    texture::TextureUnit,
+    object::Handle,
+    texture::{Texture, TextureUnit},
 };
 use gl::types::GLint;
 
 impl GLContext {
     pub fn activate_texture(
         &mut self,
-        obj_desc: GLObjectDescriptor,
+        obj_desc: Handle<Texture>,
         generate_mipmap: bool,
     ) -> Result<GLint> {
         // texture already active
@@ -49,10 +49,7 @@ impl GLContext {
         Ok(GLint::from(texture_unit))
     }
 
-    pub fn deactivate_texture(
-        &mut self,
-        obj_desc: GLObjectDescriptor,
-    ) -> Option<GLObjectDescriptor> {
+    pub fn deactivate_texture(&mut self, obj_desc: Handle<Texture>) -> Option<Handle<Texture>> {
         let (idx, od) = self
             .active_textures
             .iter_mut()
@@ -73,7 +70,7 @@ impl GLContext {
         Some(obj_desc)
     }
 
-    pub(super) fn init_texture_units() -> Result<Vec<Option<GLObjectDescriptor>>> {
+    pub(super) fn init_texture_units() -> Result<Vec<Option<Handle<Texture>>>> {
         let mut max_active_textures = 0;
         unsafe { gl::GetIntegerv(gl::MAX_TEXTURE_IMAGE_UNITS, &mut max_active_textures) };
         gl_check_err()?;
@@ -0,0 +1,122 @@
+use super::GLContext;
+use crate::{
+    error::{gl_check_err, Error, Result},
+    internal_utils::try_into,
+    object::Handle,
+    shader::program::Program,
+    uniform::{Std140, Std140Writer, UniformBuffer},
+};
+use gl::types::GLuint;
+
+impl GLContext {
+    /// Binds `ubo_desc` to uniform buffer binding point `binding`, replacing whatever buffer was
+    /// bound there. A buffer can be bound to several binding points at once.
+    pub fn try_bind_uniform_buffer(
+        &mut self,
+        ubo_desc: Handle<UniformBuffer>,
+        binding: GLuint,
+    ) -> Result<()> {
+        let idx: usize = try_into!(binding);
+        if idx >= self.uniform_buffer_bindings.len() {
+            return Err(Error::InvalidUniformBuffer(format!(
+                "binding point {binding} exceeds the maximum of {}",
+                self.uniform_buffer_bindings.len() - 1
+            )));
+        }
+        let uniform_buffer = self.get_uniform_buffer(ubo_desc)?;
+        unsafe {
+            gl::BindBufferBase(gl::UNIFORM_BUFFER, binding, uniform_buffer.gl_object_id);
+        }
+        log::debug!(
+            "bound uniform buffer {binding}: object_storage_id={ubo_desc:?} -> {uniform_buffer:?}"
+        );
+        self.uniform_buffer_bindings[idx] = Some(ubo_desc);
+        Ok(())
+    }
+
+    pub fn unbind_uniform_buffer(&mut self, binding: GLuint) -> Option<Handle<UniformBuffer>> {
+        let idx: usize = try_into!(binding);
+        let ubo_desc = self.uniform_buffer_bindings.get_mut(idx)?.take()?;
+        unsafe {
+            gl::BindBufferBase(gl::UNIFORM_BUFFER, binding, 0);
+        }
+        log::debug!("unbound uniform buffer {binding}: object_storage_id={ubo_desc:?}");
+        Some(ubo_desc)
+    }
+
+    /// Unbinds `ubo_desc` from every binding point it's bound to.
+    pub fn unbind_uniform_buffer_everywhere(&mut self, ubo_desc: Handle<UniformBuffer>) {
+        let bindings = self
+            .uniform_buffer_bindings
+            .iter()
+            .enumerate()
+            .filter(|(_, bound)| bound.is_some_and(|b| b == ubo_desc))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        for idx in bindings {
+            self.unbind_uniform_buffer(try_into!(idx));
+        }
+    }
+
+    pub fn get_bound_uniform_buffer(&self, binding: GLuint) -> Option<Handle<UniformBuffer>> {
+        let idx: usize = try_into!(binding);
+        self.uniform_buffer_bindings.get(idx).copied().flatten()
+    }
+
+    /// Overwrites the contents of `ubo_desc` starting at byte `offset`.
+    pub fn try_write_uniform_buffer(
+        &self,
+        ubo_desc: Handle<UniformBuffer>,
+        offset: usize,
+        data: &[u8],
+    ) -> Result<()> {
+        self.get_uniform_buffer(ubo_desc)?.write(offset, data)
+    }
+
+    /// Packs `value` following the std140 layout and writes it to the start of `ubo_desc`.
+    pub fn try_write_uniform_buffer_std140<T: Std140 + ?Sized>(
+        &self,
+        ubo_desc: Handle<UniformBuffer>,
+        value: &T,
+    ) -> Result<()> {
+        let mut writer = Std140Writer::new();
+        writer.write(value);
+        self.try_write_uniform_buffer(ubo_desc, 0, writer.as_bytes())
+    }
+
+    /// Assigns the uniform block named `block` in `prog_desc` to uniform buffer binding point
+    /// `binding`. The program doesn't have to be in use.
+    pub fn try_bind_uniform_block(
+        &mut self,
+        prog_desc: Handle<Program>,
+        block: &str,
+        binding: GLuint,
+    ) -> Result<()> {
+        self.get_mut(prog_desc)?
+            .bind_uniform_block(block, binding)?;
+        log::debug!("bound uniform block '{block}' of {prog_desc:?} to binding point {binding}");
+        Ok(())
+    }
+
+    /// Assigns the uniform block named `block` to `binding` in every program that declares it,
+    /// e.g. a camera block shared by all shaders. Returns the number of programs affected.
+    pub fn bind_uniform_block_in_all_programs(&mut self, block: &str, binding: GLuint) -> usize {
+        let programs = self
+            .iter::<Program>()
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        programs
+            .into_iter()
+            .filter(|&program| self.try_bind_uniform_block(program, block, binding).is_ok())
+            .count()
+    }
+
+    pub(super) fn init_uniform_buffer_bindings() -> Result<Vec<Option<Handle<UniformBuffer>>>> {
+        let mut max_bindings = 0;
+        unsafe { gl::GetIntegerv(gl::MAX_UNIFORM_BUFFER_BINDINGS, &mut max_bindings) };
+        gl_check_err()?;
+        let maximum: usize = try_into!(max_bindings);
+        log::debug!("maximum allowed uniform buffer bindings: {maximum}");
+        Ok(vec![None; maximum])
+    }
+}
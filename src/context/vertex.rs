@@ -2,29 +2,91 @@ use super::GLContext;
 use crate::{
     error::{Error, Result},
     internal_utils::try_into,
-    object::GLObjectDescriptor,
+    object::Handle,
+    vertex::{AttributeData, Primitive, Submesh, Vertex, VertexObject},
 };
-use gl::types::GLenum;
-use std::ptr;
+use gl::types::{GLenum, GLsizei};
+use std::ffi::c_void;
 
 impl GLContext {
     pub fn try_render(&self) -> Result<()> {
+        self.draw(None, None)
+    }
+
+    /// Renders `instances` instances of the bound vertex object. Per-instance attributes advance
+    /// following their divisor and shaders can tell instances apart with `gl_InstanceID`.
+    pub fn try_render_instanced(&self, instances: GLsizei) -> Result<()> {
+        self.draw(None, Some(instances))
+    }
+
+    /// Renders only the submesh `name` of the bound vertex object. See [Submesh].
+    pub fn try_render_submesh(&self, name: &str) -> Result<()> {
+        self.draw(Some(name), None)
+    }
+
+    /// Same as [GLContext::try_render_instanced] for the submesh `name` of the bound vertex
+    /// object.
+    pub fn try_render_submesh_instanced(&self, name: &str, instances: GLsizei) -> Result<()> {
+        self.draw(Some(name), Some(instances))
+    }
+
+    fn draw(&self, submesh: Option<&str>, instances: Option<GLsizei>) -> Result<()> {
         self.ensure_program_active()?;
         let obj_desc = self.bound_vertex_object.ok_or(Error::NoBoundVertexObject)?;
         let vo = self.get_vertex_object(obj_desc)?;
 
+        let range = match submesh {
+            Some(name) => *vo.get_submesh(name)?,
+            None => {
+                let count = vo
+                    .index_buffer_object
+                    .as_ref()
+                    .map_or(vo.num_vertices, |ibo| try_into!(ibo.indexes.len()));
+                Submesh::new(0, count)
+            }
+        };
+
         unsafe {
             let primitive = GLenum::from(vo.primitive);
+            if let Primitive::Patches(vertices) = vo.primitive {
+                gl::PatchParameteri(gl::PATCH_VERTICES, vertices);
+            }
 
             if let Some(ibo) = vo.index_buffer_object.as_ref() {
-                gl::DrawElements(
-                    primitive,
-                    try_into!(ibo.indexes.len()),
-                    gl::UNSIGNED_INT,
-                    ptr::null(),
-                )
+                if ibo.primitive_restart {
+                    gl::Enable(gl::PRIMITIVE_RESTART);
+                    gl::PrimitiveRestartIndex(ibo.index_type.max_value());
+                }
+                let index_type = GLenum::from(ibo.index_type);
+                let first: usize = try_into!(range.first);
+                let offset = (first * ibo.index_type.size()) as *const c_void;
+                match instances {
+                    Some(instances) => gl::DrawElementsInstancedBaseVertex(
+                        primitive,
+                        range.count,
+                        index_type,
+                        offset,
+                        instances,
+                        range.base_vertex,
+                    ),
+                    None => gl::DrawElementsBaseVertex(
+                        primitive,
+                        range.count,
+                        index_type,
+                        offset,
+                        range.base_vertex,
+                    ),
+                }
+                if ibo.primitive_restart {
+                    gl::Disable(gl::PRIMITIVE_RESTART);
+                }
             } else {
-                gl::DrawArrays(primitive, 0, try_into!(vo.num_vertices));
+                match instances {
+                    Some(instances) => {
+                        gl::DrawArraysInstanced(primitive, range.first, range.count, instances)
+                    }
+                    None => gl::DrawArrays(primitive, range.first, range.count),
+                }
             }
         }
 
@@ -33,7 +95,7 @@ impl GLContext {
 
     /// Will return if a different vertex object (i.e. VAO) is currently bound. Detach the current before
     /// binding a new one.
-    pub fn try_bind_vertex_object(&mut self, vo_desc: GLObjectDescriptor) -> Result<()> {
+    pub fn try_bind_vertex_object(&mut self, vo_desc: Handle<VertexObject>) -> Result<()> {
         if self.bound_vertex_object.is_some_and(|vo| vo == vo_desc) {
             return Ok(());
         } else if self.bound_vertex_object.is_some() {
@@ -52,7 +114,7 @@ impl GLContext {
     }
 
     /// Unbinds the current vertex object i.e. VAO.
-    pub fn unbind_current_vertex_object(&mut self) -> Option<GLObjectDescriptor> {
+    pub fn unbind_current_vertex_object(&mut self) -> Option<Handle<VertexObject>> {
         let obj_desc = self.bound_vertex_object.take()?;
         let vo = self.get_vertex_object(obj_desc).ok()?;
         unsafe {
@@ -65,11 +127,139 @@ impl GLContext {
         Some(obj_desc)
     }
 
-    pub fn vertex_object_bound(&self, vo_desc: GLObjectDescriptor) -> bool {
+    pub fn vertex_object_bound(&self, vo_desc: Handle<VertexObject>) -> bool {
         self.bound_vertex_object.is_some_and(|od| od == vo_desc)
     }
 
-    pub fn get_current_bound_vertex_object(&self) -> Option<GLObjectDescriptor> {
+    pub fn get_current_bound_vertex_object(&self) -> Option<Handle<VertexObject>> {
         self.bound_vertex_object
     }
+
+    /// Overwrites the values of attribute `name` of `vo_desc` starting at vertex `first_vertex`.
+    /// `data` must have the same type as the data the attribute was built with. The CPU-side
+    /// copy of the attribute is updated as well.
+    pub fn try_update_attribute(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        name: &str,
+        first_vertex: usize,
+        data: impl Into<AttributeData>,
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?
+            .update_attribute(name, first_vertex, data.into())
+    }
+
+    /// Replaces every value of attribute `name` of `vo_desc`; `data` must cover every vertex.
+    /// See [GLContext::try_resize_vertex_object] to change the number of vertices.
+    pub fn try_replace_attribute(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        name: &str,
+        data: impl Into<AttributeData>,
+    ) -> Result<()> {
+        let data = data.into();
+        let vo = self.get_mut(vo_desc)?;
+        let num_values = vo
+            .attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.component_size * vo.num_vertices);
+        if num_values.is_some_and(|num_values| num_values as usize != data.len()) {
+            return Err(Error::InvalidVertexObject(format!(
+                "attribute '{name}' has {} values but was given {}",
+                num_values.unwrap_or_default(),
+                data.len()
+            )));
+        }
+        vo.update_attribute(name, 0, data)
+    }
+
+    /// Overwrites the vertices of a vertex object built from interleaved vertices of type `V`,
+    /// starting at vertex `first_vertex`.
+    pub fn try_update_vertices<V: Vertex>(
+        &self,
+        vo_desc: Handle<VertexObject>,
+        first_vertex: usize,
+        vertices: &[V],
+    ) -> Result<()> {
+        self.get_vertex_object(vo_desc)?
+            .update_vertices(first_vertex, vertices)
+    }
+
+    /// Reallocates the vertex buffer of `vo_desc` to hold `num_vertices` vertices. Existing
+    /// vertices are kept, up to `num_vertices`, and new ones are zeroed until updated.
+    pub fn try_resize_vertex_object(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        num_vertices: usize,
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?.resize(num_vertices)?;
+        log::debug!("resized vertex object {vo_desc:?} to {num_vertices} vertices");
+        Ok(())
+    }
+
+    /// Replaces the indexes of `vo_desc`, which may change their number. Adds an index buffer if
+    /// the vertex object was built without one.
+    pub fn try_replace_indexes(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        indexes: &[u32],
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?.replace_indexes(indexes)?;
+
+        // The element array buffer binding belongs to the bound vertex array so restore it.
+        let bound_ibo = self
+            .bound_vertex_object
+            .and_then(|bound| self.get_vertex_object(bound).ok())
+            .and_then(|vo| vo.index_buffer_object.as_ref())
+            .map_or(0, |ibo| ibo.gl_object_id);
+        unsafe {
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, bound_ibo);
+        }
+        Ok(())
+    }
+
+    /// Overwrites the values of instance attribute `name` of `vo_desc` starting at value
+    /// `first`, e.g. every frame for moving instances. See [GLContext::try_update_attribute].
+    pub fn try_update_instance_attribute(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        name: &str,
+        first: usize,
+        data: impl Into<AttributeData>,
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?
+            .update_instance_attribute(name, first, data.into())
+    }
+
+    /// Reallocates the instance buffer of `vo_desc` to hold `len` values per instance attribute.
+    /// Existing values are kept, up to `len`, and new ones are zeroed until updated.
+    pub fn try_resize_instances(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        len: usize,
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?.resize_instances(len)?;
+        log::debug!("resized instance attributes of vertex object {vo_desc:?} to {len} values");
+        Ok(())
+    }
+
+    /// Adds or replaces the submesh `name` of `vo_desc`, e.g. after replacing its indexes.
+    pub fn try_set_submesh(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        name: &str,
+        submesh: Submesh,
+    ) -> Result<()> {
+        self.get_mut(vo_desc)?.set_submesh(name, submesh)
+    }
+
+    /// Removes the submesh `name` of `vo_desc`, returning it if it existed.
+    pub fn try_remove_submesh(
+        &mut self,
+        vo_desc: Handle<VertexObject>,
+        name: &str,
+    ) -> Result<Option<Submesh>> {
+        Ok(self.get_mut(vo_desc)?.remove_submesh(name))
+    }
 }
@@ -1,5 +1,6 @@
 use crate::object::GLObjectDescriptor;
-use gl::types::GLenum;
+use gl::types::{GLenum, GLint};
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[macro_export]
@@ -45,9 +46,66 @@ pub enum Error {
     #[error("uniform '{0}' not found in program associated with mesh")]
     UniformDoesNotExist(String),
 
+    #[error(
+        "'{0}' is not an active uniform of the program; it may be misspelled or optimized out"
+    )]
+    InactiveUniform(String),
+
+    #[error("uniform '{0}' is in a uniform block and can only be set through a uniform buffer")]
+    UniformInBlock(String),
+
+    #[error(
+        "'{0}' is not an active attribute of the program; it may be misspelled or optimized out"
+    )]
+    InactiveAttribute(String),
+
+    #[error("uniform '{name}' is a {expected} in the program but was given a {found}")]
+    UniformTypeMismatch {
+        name: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    #[error("uniform '{name}' has {count} elements but only {available} fit in the array")]
+    UniformArrayOverflow {
+        name: String,
+        count: usize,
+        available: usize,
+    },
+
+    #[error("can't set element {index} of uniform '{name}' to a {found}")]
+    InvalidUniformElement {
+        name: String,
+        index: usize,
+        found: &'static str,
+    },
+
+    #[error("attribute '{name}' is a {expected} in the program but was given {found} components")]
+    AttributeTypeMismatch {
+        name: String,
+        expected: &'static str,
+        found: i32,
+    },
+
+    #[error("'{0}' is not an active uniform block of the program")]
+    UniformBlockNotFound(String),
+
+    #[error("invalid uniform buffer operation: {0}")]
+    InvalidUniformBuffer(String),
+
     #[error("an error occurred while linking program: {0}")]
     ProgramLink(String),
 
+    #[error("invalid combination of shader stages: {0}")]
+    InvalidProgramStages(String),
+
+    #[error("{feature} requires OpenGL {}.{} but the context is OpenGL {}.{}", .required.0, .required.1, .current.0, .current.1)]
+    UnsupportedGLVersion {
+        feature: &'static str,
+        required: (GLint, GLint),
+        current: (GLint, GLint),
+    },
+
     #[error("can't use program while another is in use")]
     AnotherProgramInUse,
 
@@ -57,6 +115,29 @@ pub enum Error {
     #[error("an error occurred while compiling {shader_name}: {reason}")]
     ShaderCompile { shader_name: String, reason: String },
 
+    #[error("failed to resolve shader include: {0}")]
+    ShaderInclude(String),
+
+    #[error("can't bind framebuffer while another is bound")]
+    AnotherFramebufferBound,
+
+    #[error("invalid framebuffer configuration: {0}")]
+    InvalidFramebuffer(String),
+
+    #[error("framebuffer is incomplete: {0}")]
+    IncompleteFramebuffer(&'static str),
+
+    #[error("no reference image found at {}; set GLOAM_UPDATE_SNAPSHOTS=1 to create it", .0.display())]
+    SnapshotMissing(PathBuf),
+
+    #[error("snapshot '{name}' has {differing_pixels} differing pixels (max {max_differing_pixels}); see {}", .diff_path.display())]
+    SnapshotMismatch {
+        name: String,
+        differing_pixels: usize,
+        max_differing_pixels: usize,
+        diff_path: PathBuf,
+    },
+
     #[error("all texture units are currently active")]
     MaxActiveTextures,
 
@@ -72,6 +153,9 @@ pub enum Error {
     #[error("invalid vertex object configuration: {0}")]
     InvalidVertexObject(String),
 
+    #[error("vertex object has no submesh named '{0}'")]
+    SubmeshNotFound(String),
+
     #[error("no corresponding texture unit for value {0}")]
     InvalidTextureUnit(usize),
 
@@ -0,0 +1,33 @@
+use gl::types::GLenum;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DepthStencilFormat {
+    Depth16,
+    Depth24,
+    Depth32F,
+    Depth24Stencil8,
+    Depth32FStencil8,
+}
+
+impl DepthStencilFormat {
+    pub(crate) fn internal_format(self) -> GLenum {
+        match self {
+            DepthStencilFormat::Depth16 => gl::DEPTH_COMPONENT16,
+            DepthStencilFormat::Depth24 => gl::DEPTH_COMPONENT24,
+            DepthStencilFormat::Depth32F => gl::DEPTH_COMPONENT32F,
+            DepthStencilFormat::Depth24Stencil8 => gl::DEPTH24_STENCIL8,
+            DepthStencilFormat::Depth32FStencil8 => gl::DEPTH32F_STENCIL8,
+        }
+    }
+
+    pub(crate) fn attachment_point(self) -> GLenum {
+        match self {
+            DepthStencilFormat::Depth16
+            | DepthStencilFormat::Depth24
+            | DepthStencilFormat::Depth32F => gl::DEPTH_ATTACHMENT,
+            DepthStencilFormat::Depth24Stencil8 | DepthStencilFormat::Depth32FStencil8 => {
+                gl::DEPTH_STENCIL_ATTACHMENT
+            }
+        }
+    }
+}
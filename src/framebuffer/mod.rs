@@ -0,0 +1,269 @@
+use crate::{
+    context::GLContext,
+    error::{Error, Result},
+    internal_utils::try_into,
+    object::Handle,
+    texture::{Texture, TextureBuilder},
+};
+use gl::types::{GLenum, GLuint};
+use std::{fmt::Debug, ops::Drop};
+
+pub mod attachment;
+pub use attachment::DepthStencilFormat;
+
+/// An off-screen render target. Color attachments are regular textures that live in the object
+/// registry, so they can be sampled like any other texture once rendering into them is done.
+/// Depth and stencil attachments are renderbuffers owned by the [Framebuffer] itself.
+pub struct Framebuffer {
+    pub gl_object_id: GLuint,
+    pub width: u32,
+    pub height: u32,
+    color_attachments: Vec<Handle<Texture>>,
+    depth_stencil_attachment: Option<Renderbuffer>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Renderbuffer {
+    gl_object_id: GLuint,
+    format: DepthStencilFormat,
+}
+
+#[derive(Debug)]
+pub struct FramebufferBuilder {
+    width: u32,
+    height: u32,
+    color_attachments: Vec<TextureBuilder>,
+    depth_stencil_attachment: Option<DepthStencilFormat>,
+}
+
+impl Framebuffer {
+    /// Textures attached to `GL_COLOR_ATTACHMENT0`, `GL_COLOR_ATTACHMENT1`, etc. in that order.
+    pub fn color_attachments(&self) -> &[Handle<Texture>] {
+        &self.color_attachments
+    }
+
+    pub fn depth_stencil_format(&self) -> Option<DepthStencilFormat> {
+        self.depth_stencil_attachment.as_ref().map(|rb| rb.format)
+    }
+}
+
+impl FramebufferBuilder {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            color_attachments: Vec::new(),
+            depth_stencil_attachment: None,
+        }
+    }
+
+    /// Color attachments are assigned consecutive attachment points in the order they're added.
+    /// The texture must have the same dimensions as the framebuffer.
+    pub fn color_attachment(mut self, texture: TextureBuilder) -> Result<Self> {
+        if texture.dimensions() != (self.width, self.height) {
+            let (width, height) = texture.dimensions();
+            return Err(Error::InvalidFramebuffer(format!(
+                "color attachment is {width}x{height} but framebuffer is {}x{}",
+                self.width, self.height
+            )));
+        }
+        self.color_attachments.push(texture);
+        Ok(self)
+    }
+
+    pub fn depth_stencil_attachment(mut self, format: DepthStencilFormat) -> Self {
+        self.depth_stencil_attachment = Some(format);
+        self
+    }
+
+    pub fn build(self, ctx: &mut GLContext) -> Result<Handle<Framebuffer>> {
+        unsafe { self.build_impl(ctx) }
+    }
+
+    unsafe fn build_impl(self, ctx: &mut GLContext) -> Result<Handle<Framebuffer>> {
+        let FramebufferBuilder {
+            width,
+            height,
+            color_attachments,
+            depth_stencil_attachment,
+        } = self;
+
+        if color_attachments.is_empty() && depth_stencil_attachment.is_none() {
+            return Err(Error::InvalidFramebuffer(
+                "framebuffer must have at least one attachment".to_string(),
+            ));
+        }
+
+        let mut textures = Vec::with_capacity(color_attachments.len());
+        for texture_builder in color_attachments {
+            match texture_builder.build(ctx) {
+                Ok(handle) => textures.push(handle),
+                Err(e) => {
+                    remove_textures(ctx, &textures);
+                    return Err(e);
+                }
+            }
+        }
+
+        let previously_bound = ctx
+            .get_current_bound_framebuffer()
+            .and_then(|desc| ctx.get_framebuffer(desc).ok())
+            .map_or(0, |fb| fb.gl_object_id);
+
+        let mut fbo = 0;
+        gl::GenFramebuffers(1, &mut fbo);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+        let attached = Self::attach(ctx, &textures, depth_stencil_attachment, width, height);
+        let status = gl::CheckFramebufferStatus(gl::FRAMEBUFFER);
+        gl::BindFramebuffer(gl::FRAMEBUFFER, previously_bound);
+
+        let depth_stencil_attachment = match attached {
+            Ok(depth_stencil_attachment) => depth_stencil_attachment,
+            Err(e) => {
+                gl::DeleteFramebuffers(1, &fbo);
+                remove_textures(ctx, &textures);
+                return Err(e);
+            }
+        };
+
+        let framebuffer = Framebuffer {
+            width,
+            height,
+            depth_stencil_attachment,
+            color_attachments: textures,
+            gl_object_id: fbo,
+        };
+
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            remove_textures(ctx, &framebuffer.color_attachments);
+            return Err(Error::IncompleteFramebuffer(framebuffer_status_display(
+                status,
+            )));
+        }
+
+        let handle = ctx.register(framebuffer);
+        Ok(handle)
+    }
+
+    /// Attaches `textures` and a new depth/stencil renderbuffer, if any, to the bound framebuffer.
+    unsafe fn attach(
+        ctx: &GLContext,
+        textures: &[Handle<Texture>],
+        depth_stencil_attachment: Option<DepthStencilFormat>,
+        width: u32,
+        height: u32,
+    ) -> Result<Option<Renderbuffer>> {
+        let mut draw_buffers = Vec::with_capacity(textures.len());
+        for (i, handle) in textures.iter().enumerate() {
+            let texture = ctx.get_texture(*handle)?;
+            let attachment_point = gl::COLOR_ATTACHMENT0 + GLenum::try_from(i).unwrap();
+            gl::FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                attachment_point,
+                texture.kind.into(),
+                texture.gl_object_id,
+                0,
+            );
+            draw_buffers.push(attachment_point);
+        }
+        if draw_buffers.is_empty() {
+            gl::DrawBuffer(gl::NONE);
+            gl::ReadBuffer(gl::NONE);
+        } else {
+            gl::DrawBuffers(try_into!(draw_buffers.len()), draw_buffers.as_ptr());
+        }
+
+        let depth_stencil_attachment = depth_stencil_attachment.map(|format| {
+            let mut rbo = 0;
+            gl::GenRenderbuffers(1, &mut rbo);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, rbo);
+            gl::RenderbufferStorage(
+                gl::RENDERBUFFER,
+                format.internal_format(),
+                try_into!(width),
+                try_into!(height),
+            );
+            gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+            gl::FramebufferRenderbuffer(
+                gl::FRAMEBUFFER,
+                format.attachment_point(),
+                gl::RENDERBUFFER,
+                rbo,
+            );
+            Renderbuffer {
+                format,
+                gl_object_id: rbo,
+            }
+        });
+        Ok(depth_stencil_attachment)
+    }
+}
+
+/// Removes the textures created for the attachments of a framebuffer that failed to build.
+fn remove_textures(ctx: &mut GLContext, textures: &[Handle<Texture>]) {
+    for handle in textures {
+        ctx.remove_object(*handle);
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteFramebuffers(1, &self.gl_object_id);
+        }
+        self.gl_object_id = 0;
+    }
+}
+
+impl Drop for Renderbuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteRenderbuffers(1, &self.gl_object_id);
+        }
+        self.gl_object_id = 0;
+    }
+}
+
+impl Debug for Framebuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Framebuffer {{ gl_object_id={}, size={}x{}, color_attachments={:?}, depth_stencil={:?} }}",
+            self.gl_object_id,
+            self.width,
+            self.height,
+            self.color_attachments,
+            self.depth_stencil_format(),
+        )
+    }
+}
+
+fn framebuffer_status_display(status: GLenum) -> &'static str {
+    match status {
+        gl::FRAMEBUFFER_UNDEFINED => {
+            "GL_FRAMEBUFFER_UNDEFINED: The default framebuffer does not exist."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => {
+            "GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT: An attachment point is framebuffer incomplete."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => {
+            "GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT: No image is attached to the framebuffer."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => {
+            "GL_FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER: A draw buffer names a missing attachment."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER => {
+            "GL_FRAMEBUFFER_INCOMPLETE_READ_BUFFER: The read buffer names a missing attachment."
+        }
+        gl::FRAMEBUFFER_UNSUPPORTED => {
+            "GL_FRAMEBUFFER_UNSUPPORTED: The combination of internal formats is not supported."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => {
+            "GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE: Attachments don't share the same sample count."
+        }
+        gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => {
+            "GL_FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS: Attachments aren't all layered."
+        }
+        _ => "unknown framebuffer status",
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    error::{Error, Result},
+    internal_utils::try_into,
+};
+use khronos_egl as egl;
+use std::ptr;
+
+/// `EGL_PLATFORM_SURFACELESS_MESA`; lets Mesa create a display without any windowing system.
+const PLATFORM_SURFACELESS_MESA: egl::Enum = 0x31DD;
+
+/// An OpenGL context backed by an EGL pixel buffer rather than a window. Useful for rendering on
+/// machines without a display e.g. CI runners using Mesa's llvmpipe software rasterizer. The
+/// pixel buffer acts as the default framebuffer, so everything that works against a [Window]'s
+/// framebuffer works here as well.
+///
+/// [Window]: crate::window::Window
+pub struct Headless {
+    egl: egl::Instance<egl::Static>,
+    display: egl::Display,
+    context: egl::Context,
+    surface: egl::Surface,
+    width: u32,
+    height: u32,
+}
+
+/// Defaults to an OpenGL 3.3 core context. Tessellation requires OpenGL 4.0 and compute shaders
+/// OpenGL 4.3, so set the version to e.g. 4.5 to use them.
+pub struct HeadlessConfig {
+    pub gl_major_version: u32,
+    pub gl_minor_version: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Bits per pixel of the pixel buffer's stencil buffer.
+    pub stencil_bits: u32,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            gl_major_version: 3,
+            gl_minor_version: 3,
+            width: 800,
+            height: 600,
+            stencil_bits: 8,
+        }
+    }
+}
+
+impl Headless {
+    /// Creates an EGL context, makes it current on the calling thread, and loads the OpenGL
+    /// function pointers. All subsequent OpenGL calls must come from this same thread.
+    pub fn new(config: HeadlessConfig) -> Result<Self> {
+        let HeadlessConfig {
+            gl_major_version,
+            gl_minor_version,
+            width,
+            height,
+            stencil_bits,
+        } = config;
+
+        let egl = egl::Instance::new(egl::Static);
+        let display = Self::get_display(&egl)?;
+        egl.initialize(display).map_err(Error::boxed)?;
+        egl.bind_api(egl::OPENGL_API).map_err(Error::boxed)?;
+
+        let config_attribs = [
+            egl::SURFACE_TYPE,
+            egl::PBUFFER_BIT,
+            egl::RENDERABLE_TYPE,
+            egl::OPENGL_BIT,
+            egl::RED_SIZE,
+            8,
+            egl::GREEN_SIZE,
+            8,
+            egl::BLUE_SIZE,
+            8,
+            egl::ALPHA_SIZE,
+            8,
+            egl::DEPTH_SIZE,
+            24,
+            egl::STENCIL_SIZE,
+            try_into!(stencil_bits),
+            egl::NONE,
+        ];
+        let egl_config = egl
+            .choose_first_config(display, &config_attribs)
+            .map_err(Error::boxed)?
+            .ok_or(Error::Misc(
+                "no EGL config supports headless OpenGL rendering".to_string(),
+            ))?;
+
+        let surface_attribs = [
+            egl::WIDTH,
+            try_into_egl_int(width)?,
+            egl::HEIGHT,
+            try_into_egl_int(height)?,
+            egl::NONE,
+        ];
+        let surface = egl
+            .create_pbuffer_surface(display, egl_config, &surface_attribs)
+            .map_err(Error::boxed)?;
+
+        let context_attribs = [
+            egl::CONTEXT_MAJOR_VERSION,
+            try_into_egl_int(gl_major_version)?,
+            egl::CONTEXT_MINOR_VERSION,
+            try_into_egl_int(gl_minor_version)?,
+            egl::CONTEXT_OPENGL_PROFILE_MASK,
+            egl::CONTEXT_OPENGL_CORE_PROFILE_BIT,
+            egl::NONE,
+        ];
+        let context = egl
+            .create_context(display, egl_config, None, &context_attribs)
+            .map_err(Error::boxed)?;
+
+        egl.make_current(display, Some(surface), Some(surface), Some(context))
+            .map_err(Error::boxed)?;
+
+        gl::load_with(|sym| {
+            egl.get_proc_address(sym)
+                .map_or(ptr::null(), |f| f as *const _)
+        });
+
+        log::debug!("initialized headless OpenGL {gl_major_version}.{gl_minor_version} context: {width}x{height}");
+
+        Ok(Self {
+            egl,
+            display,
+            context,
+            surface,
+            width,
+            height,
+        })
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    pub fn get_aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height as f32
+    }
+
+    /// Prefers Mesa's surfaceless platform and falls back to the default display, which may
+    /// itself require a running display server.
+    fn get_display(egl: &egl::Instance<egl::Static>) -> Result<egl::Display> {
+        let surfaceless = unsafe {
+            egl.get_platform_display(
+                PLATFORM_SURFACELESS_MESA,
+                egl::DEFAULT_DISPLAY,
+                &[egl::ATTRIB_NONE],
+            )
+        };
+        if let Ok(display) = surfaceless {
+            return Ok(display);
+        }
+        unsafe { egl.get_display(egl::DEFAULT_DISPLAY) }
+            .ok_or(Error::Misc("failed to acquire an EGL display".to_string()))
+    }
+}
+
+impl Drop for Headless {
+    fn drop(&mut self) {
+        let _ = self.egl.make_current(self.display, None, None, None);
+        let _ = self.egl.destroy_surface(self.display, self.surface);
+        let _ = self.egl.destroy_context(self.display, self.context);
+        let _ = self.egl.terminate(self.display);
+    }
+}
+
+fn try_into_egl_int(value: u32) -> Result<egl::Int> {
+    egl::Int::try_from(value).map_err(Error::boxed)
+}
@@ -6,6 +6,16 @@ macro_rules! try_into {
 }
 pub use try_into;
 
+/// Version of the current OpenGL context as `(major, minor)`.
+pub fn gl_version() -> (gl::types::GLint, gl::types::GLint) {
+    let (mut major, mut minor) = (0, 0);
+    unsafe {
+        gl::GetIntegerv(gl::MAJOR_VERSION, &mut major);
+        gl::GetIntegerv(gl::MINOR_VERSION, &mut minor);
+    }
+    (major, minor)
+}
+
 pub fn as_gl_bool(val: bool) -> gl::types::GLboolean {
     if val {
         gl::TRUE
@@ -5,11 +5,17 @@ pub mod app;
 pub mod camera;
 pub mod context;
 pub mod error;
+pub mod framebuffer;
+#[cfg(feature = "headless")]
+pub mod headless;
 pub mod mesh;
 pub mod mouse;
 pub mod object;
 pub mod physics;
 pub mod shader;
+#[cfg(feature = "headless")]
+pub mod snapshot;
+pub mod state;
 pub mod texture;
 pub mod uniform;
 pub mod vertex;
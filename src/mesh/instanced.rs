@@ -0,0 +1,137 @@
+use super::Mesh;
+use crate::{
+    context::GLContext,
+    error::{Error, Result},
+    object::Handle,
+    shader::program::Program,
+    uniform::Uniform,
+    vertex::{AttributeData, VertexObject},
+};
+use gl::types::GLsizei;
+use std::ops::{Deref, DerefMut};
+
+/// A [Mesh] rendered as many instances with a single draw call. Per-instance values come from
+/// the instance attributes of its vertex object, see
+/// [VertexObjectBuilder::instance_attribute](crate::vertex::VertexObjectBuilder::instance_attribute),
+/// or from `gl_InstanceID`. Uniforms and submeshes are managed through the underlying [Mesh],
+/// while every render method of [Mesh] has an instanced counterpart here.
+#[derive(Debug)]
+pub struct InstancedMesh {
+    mesh: Mesh,
+    instances: GLsizei,
+}
+
+impl InstancedMesh {
+    /// Same as [Mesh::new], rendering as many instances as the instance attributes of `vo_desc`
+    /// cover.
+    pub fn new(
+        ctx: &GLContext,
+        vo_desc: Handle<VertexObject>,
+        program_desc: Handle<Program>,
+        uniforms: Vec<Uniform>,
+    ) -> Result<Self> {
+        let instances = ctx
+            .get_vertex_object(vo_desc)?
+            .num_instances()
+            .ok_or_else(|| {
+                Error::InvalidVertexObject(
+                    "vertex object has no instance attributes to count instances with".to_string(),
+                )
+            })?;
+        let mesh = Mesh::new(ctx, vo_desc, program_desc, uniforms)?;
+        Ok(Self::from_mesh(mesh, instances))
+    }
+
+    /// Renders `mesh` as `instances` instances.
+    pub fn from_mesh(mesh: Mesh, instances: GLsizei) -> Self {
+        Self { mesh, instances }
+    }
+
+    pub fn instances(&self) -> GLsizei {
+        self.instances
+    }
+
+    /// Number of instances to render, e.g. after resizing the instance attributes with
+    /// [GLContext::try_resize_instances].
+    pub fn set_instances(&mut self, instances: GLsizei) {
+        self.instances = instances;
+    }
+
+    /// Overwrites the values of instance attribute `name` starting at value `first`. See
+    /// [GLContext::try_update_instance_attribute].
+    pub fn try_update_instance_attribute(
+        &self,
+        ctx: &mut GLContext,
+        name: &str,
+        first: usize,
+        data: impl Into<AttributeData>,
+    ) -> Result<()> {
+        ctx.try_update_instance_attribute(self.mesh.vertex_object, name, first, data)
+    }
+
+    /// Bind vertex array object, use program, send uniforms that changed to the program, and
+    /// render every instance.
+    pub fn try_render(&self, ctx: &mut GLContext) -> Result<()> {
+        ctx.try_use_program(self.mesh.program)?;
+        ctx.try_bind_vertex_object(self.mesh.vertex_object)?;
+        self.mesh.try_set_changed_uniforms_on_gpu(ctx)?;
+        self.draw(ctx)
+    }
+
+    /// Same as [Mesh::try_render_with_current_ogl_state], rendering every instance.
+    pub fn try_render_with_current_ogl_state(&self, ctx: &mut GLContext) -> Result<()> {
+        self.draw(ctx)
+    }
+
+    fn draw(&self, ctx: &GLContext) -> Result<()> {
+        match &self.mesh.submesh {
+            Some(name) => ctx.try_render_submesh_instanced(name, self.instances),
+            None => ctx.try_render_instanced(self.instances),
+        }
+    }
+
+    /// Same as [Mesh::try_set_uniforms_and_render_with_current_ogl_state], rendering every
+    /// instance.
+    pub fn try_set_uniforms_and_render_with_current_ogl_state(
+        &mut self,
+        ctx: &mut GLContext,
+        updates: Vec<Uniform>,
+    ) -> Result<()> {
+        for uniform in updates {
+            let name = uniform.name.clone();
+            self.mesh.set_uniform_on_cpu(uniform)?;
+            self.mesh.try_set_uniform_on_gpu(ctx, &name)?;
+        }
+        self.try_render_with_current_ogl_state(ctx)
+    }
+
+    /// Same as [Mesh::try_set_uniforms_and_render], rendering every instance.
+    pub fn try_set_uniforms_and_render(
+        &mut self,
+        ctx: &mut GLContext,
+        updates: Vec<Uniform>,
+    ) -> Result<()> {
+        for uniform in updates {
+            self.mesh.set_uniform_on_cpu(uniform)?;
+        }
+        self.try_render(ctx)
+    }
+
+    pub fn into_mesh(self) -> Mesh {
+        self.mesh
+    }
+}
+
+impl Deref for InstancedMesh {
+    type Target = Mesh;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mesh
+    }
+}
+
+impl DerefMut for InstancedMesh {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.mesh
+    }
+}
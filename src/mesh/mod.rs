@@ -1,54 +1,151 @@
 use crate::{
     context::GLContext,
     error::{Error, Result},
-    object::GLObjectDescriptor,
+    object::{owned::Owner, Handle, OwnedHandle},
+    shader::program::Program,
     uniform::Uniform,
+    vertex::VertexObject,
 };
+use std::{
+    cell::Cell,
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+pub mod instanced;
+pub use instanced::InstancedMesh;
+
+static NEXT_MESH_ID: AtomicU64 = AtomicU64::new(0);
 
 /// An object containing all necessary information to render a complete surface.
 /// Even if you do not plan to set your uniforms at the time you're initializing
 /// your [Mesh], you should still register all the uniforms you plan to use.
 /// Make use of methods such as [Uniform::default_1d] to initialize zero value uniforms.
+///
+/// Uniforms are only sent to the GPU when they change, unless another [Mesh] sharing the same
+/// program sent its own uniforms in the meantime.
 #[derive(Debug)]
 pub struct Mesh {
-    vertex_object: GLObjectDescriptor,
-    program: GLObjectDescriptor,
+    id: u64,
+    vertex_object: Handle<VertexObject>,
+    /// Name of the only submesh of the vertex object to render, if any.
+    submesh: Option<String>,
+    program: Handle<Program>,
     uniforms: Vec<Uniform>,
+    /// Parallel to `uniforms`; set for uniforms that changed since they were last sent.
+    dirty: Vec<Cell<bool>>,
+    owned: Vec<Rc<Owner>>,
 }
 
 impl Mesh {
-    /// Initialize a new [Mesh]. This does not garauntee that the program passed to it is not in
+    /// Initialize a new [Mesh]. Every uniform must be an active uniform of `program_desc` with a
+    /// matching type, outside of any uniform block. This does not garauntee that the program passed to it is not in
     /// use and that the vertex object is not bound; that depends on what happened to those before
     /// this function was called.
     pub fn new(
         ctx: &GLContext,
-        vo_desc: GLObjectDescriptor,
-        program_desc: GLObjectDescriptor,
+        vo_desc: Handle<VertexObject>,
+        program_desc: Handle<Program>,
         uniforms: Vec<Uniform>,
     ) -> Result<Self> {
         // Make sure objects are in registry
         let _vo = ctx.get_vertex_object(vo_desc)?;
-        let _p = ctx.get_program(program_desc)?;
+        let program = ctx.get_program(program_desc)?;
+
+        for uniform in &uniforms {
+            let active = program.active_uniform(&uniform.name).ok_or_else(|| {
+                if program.is_block_uniform(&uniform.name) {
+                    Error::UniformInBlock(uniform.name.clone())
+                } else {
+                    Error::InactiveUniform(uniform.name.clone())
+                }
+            })?;
+            if !uniform.kind.matches_gl_type(active.gl_type) {
+                return Err(Error::UniformTypeMismatch {
+                    name: uniform.name.clone(),
+                    expected: active.type_name(),
+                    found: uniform.kind.glsl_name(),
+                });
+            }
+            // Arrays can be set starting at any element e.g. `lights[2]`.
+            let first = element_index(&uniform.name).unwrap_or(0);
+            let available = usize::try_from(active.size)
+                .unwrap_or(0)
+                .saturating_sub(first);
+            if uniform.kind.count() > available {
+                return Err(Error::UniformArrayOverflow {
+                    name: uniform.name.clone(),
+                    count: uniform.kind.count(),
+                    available,
+                });
+            }
+        }
 
         Ok(Mesh {
+            id: NEXT_MESH_ID.fetch_add(1, Ordering::Relaxed),
+            dirty: uniforms.iter().map(|_| Cell::new(true)).collect(),
             uniforms,
             vertex_object: vo_desc,
+            submesh: None,
             program: program_desc,
+            owned: Vec::new(),
         })
     }
 
-    /// Bind vertex array object, use program, set uniforms on program, and render.
+    /// Same as [Mesh::new] but the [Mesh] keeps the vertex object and program alive for as long
+    /// as it exists. See [OwnedHandle].
+    pub fn new_owned(
+        ctx: &GLContext,
+        vertex_object: &OwnedHandle<VertexObject>,
+        program: &OwnedHandle<Program>,
+        uniforms: Vec<Uniform>,
+    ) -> Result<Self> {
+        let mut mesh = Self::new(ctx, vertex_object.handle(), program.handle(), uniforms)?;
+        mesh.retain(vertex_object);
+        mesh.retain(program);
+        Ok(mesh)
+    }
+
+    /// Renders only the submesh `name` of the vertex object rather than all of it, so that
+    /// meshes with different programs or uniforms can share a vertex object. See
+    /// [Submesh](crate::vertex::Submesh).
+    pub fn with_submesh(mut self, ctx: &GLContext, name: &str) -> Result<Self> {
+        ctx.get_vertex_object(self.vertex_object)?
+            .get_submesh(name)?;
+        self.submesh = Some(name.to_string());
+        Ok(self)
+    }
+
+    pub fn submesh(&self) -> Option<&str> {
+        self.submesh.as_deref()
+    }
+
+    /// Keeps the object behind `owned` alive for as long as this [Mesh] exists, e.g. textures
+    /// sampled by its program.
+    pub fn retain<T>(&mut self, owned: &OwnedHandle<T>) {
+        self.owned.push(owned.owner());
+    }
+
+    /// Bind vertex array object, use program, send uniforms that changed to the program, and
+    /// render.
     pub fn try_render(&self, ctx: &mut GLContext) -> Result<()> {
         ctx.try_use_program(self.program)?;
         ctx.try_bind_vertex_object(self.vertex_object)?;
-        self.try_set_uniforms_on_gpu(ctx)?;
-        ctx.try_render()
+        self.try_set_changed_uniforms_on_gpu(ctx)?;
+        self.draw(ctx)
     }
 
     /// Will attempt to render [Mesh] using the current OpenGL state AS IS i.e. the currently bound
     /// vertex array object, the active program, and all the current uniforms set on that program.
     pub fn try_render_with_current_ogl_state(&self, ctx: &mut GLContext) -> Result<()> {
-        ctx.try_render()
+        self.draw(ctx)
+    }
+
+    fn draw(&self, ctx: &GLContext) -> Result<()> {
+        match &self.submesh {
+            Some(name) => ctx.try_render_submesh(name),
+            None => ctx.try_render(),
+        }
     }
 
     /// Will attempt to render the [Mesh] using the current OpenGL state after attempting
@@ -59,14 +156,15 @@ impl Mesh {
         updates: Vec<Uniform>,
     ) -> Result<()> {
         for uniform in updates {
-            self.try_set_uniform_on_gpu(ctx, &uniform.name)?;
+            let name = uniform.name.clone();
             self.set_uniform_on_cpu(uniform)?;
+            self.try_set_uniform_on_gpu(ctx, &name)?;
         }
         self.try_render_with_current_ogl_state(ctx)
     }
 
-    /// Updates uniforms on the CPU, binds, vertex array object, uses program, sends all uniforms
-    /// of this [Mesh] to the GPU, and renders.
+    /// Updates uniforms on the CPU, binds, vertex array object, uses program, sends the uniforms
+    /// of this [Mesh] that changed to the GPU, and renders.
     pub fn try_set_uniforms_and_render(
         &mut self,
         ctx: &mut GLContext,
@@ -82,10 +180,34 @@ impl Mesh {
     /// with the given name, then an error will be returned. Note that this only updates
     /// the uniform CPU-side and will not send the uniforms to the GPU. Use [Mesh::try_set_uniforms]
     /// to send the uniforms to the GPU.
+    ///
+    /// Array uniforms are replaced as a whole; a single element can be updated by naming it,
+    /// e.g. `weights[2]`, and giving a non-array value.
     pub fn set_uniform_on_cpu(&mut self, update: Uniform) -> Result<()> {
-        for uniform in self.uniforms.iter_mut() {
+        for (uniform, dirty) in self.uniforms.iter_mut().zip(&self.dirty) {
             if uniform.name == update.name {
                 *uniform = update;
+                dirty.set(true);
+                return Ok(());
+            }
+        }
+
+        let element = update
+            .name
+            .strip_suffix(']')
+            .and_then(|n| n.rsplit_once('['));
+        if let Some((array, idx)) = element {
+            let position = self.uniforms.iter().position(|u| u.name == array);
+            if let (Some(pos), Ok(idx)) = (position, idx.parse::<usize>()) {
+                let found = update.kind.glsl_name();
+                if !self.uniforms[pos].kind.set_element(idx, update.kind) {
+                    return Err(Error::InvalidUniformElement {
+                        name: array.to_string(),
+                        index: idx,
+                        found,
+                    });
+                }
+                self.dirty[pos].set(true);
                 return Ok(());
             }
         }
@@ -95,7 +217,31 @@ impl Mesh {
     /// Send all uniforms of this mesh to GPU.
     pub fn try_set_uniforms_on_gpu(&self, ctx: &GLContext) -> Result<()> {
         for uniform in &self.uniforms {
-            ctx.try_set_uniform(uniform)?;
+            ctx.try_set_uniform_impl(uniform)?;
+        }
+        let program = ctx.get_program(ctx.try_get_active_program()?)?;
+        if self.owns_active_program(ctx) {
+            self.dirty.iter().for_each(|dirty| dirty.set(false));
+            program.set_last_uploader(Some(self.id));
+        } else {
+            program.set_last_uploader(None);
+        }
+        Ok(())
+    }
+
+    /// Sends only the uniforms that changed since they were last sent. Everything is sent if
+    /// the program's uniforms were set by anything other than this [Mesh] in the meantime.
+    pub fn try_set_changed_uniforms_on_gpu(&self, ctx: &GLContext) -> Result<()> {
+        let up_to_date = self.owns_active_program(ctx)
+            && ctx.get_program(self.program)?.last_uploader() == Some(self.id);
+        if !up_to_date {
+            return self.try_set_uniforms_on_gpu(ctx);
+        }
+        for (uniform, dirty) in self.uniforms.iter().zip(&self.dirty) {
+            if dirty.get() {
+                ctx.try_set_uniform_impl(uniform)?;
+                dirty.set(false);
+            }
         }
         Ok(())
     }
@@ -103,10 +249,23 @@ impl Mesh {
     /// Sets a single uniform given by `name` in the GPU. If that uniform does not exist
     /// in the program assocaited with this [Mesh] then an error will be returned.
     pub fn try_set_uniform_on_gpu(&self, ctx: &GLContext, name: &str) -> Result<()> {
-        let Some(uniform) = self.uniforms.iter().find(|u| u.name.as_str() == name) else {
+        let Some(idx) = self.uniforms.iter().position(|u| u.name.as_str() == name) else {
             return Err(Error::UniformDoesNotExist(name.to_string()));
         };
-        ctx.try_set_uniform(uniform)
+        ctx.try_set_uniform_impl(&self.uniforms[idx])?;
+
+        let program = ctx.get_program(ctx.try_get_active_program()?)?;
+        if self.owns_active_program(ctx) && program.last_uploader() == Some(self.id) {
+            self.dirty[idx].set(false);
+        } else {
+            program.set_last_uploader(None);
+        }
+        Ok(())
+    }
+
+    fn owns_active_program(&self, ctx: &GLContext) -> bool {
+        ctx.try_get_active_program()
+            .is_ok_and(|desc| desc == self.program)
     }
 
     pub fn deactivate(&self, ctx: &mut GLContext) {
@@ -124,3 +283,8 @@ impl Mesh {
         }
     }
 }
+
+/// Index of the array element named by `name` e.g. 2 for `lights[2]`.
+fn element_index(name: &str) -> Option<usize> {
+    name.strip_suffix(']')?.rsplit_once('[')?.1.parse().ok()
+}
@@ -1,39 +1,55 @@
 use std::fmt::Debug;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct GLObjectDescriptor {
-    pub(super) internal_id: usize,
+    pub(super) generation: usize,
     pub(super) kind: GLObjectDescriptorKind,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GLObjectDescriptorKind {
     VertexObject(usize),
     Program(usize),
     Texture(usize),
+    Framebuffer(usize),
+    UniformBuffer(usize),
 }
 
 impl GLObjectDescriptor {
-    pub fn new_vertex_object_descriptor(internal_id: usize, id: usize) -> Self {
+    pub fn new_vertex_object_descriptor(generation: usize, id: usize) -> Self {
         Self {
-            internal_id,
+            generation,
             kind: GLObjectDescriptorKind::VertexObject(id),
         }
     }
 
-    pub fn new_program_descriptor(internal_id: usize, id: usize) -> Self {
+    pub fn new_program_descriptor(generation: usize, id: usize) -> Self {
         Self {
-            internal_id,
+            generation,
             kind: GLObjectDescriptorKind::Program(id),
         }
     }
 
-    pub fn new_texture_descriptor(internal_id: usize, id: usize) -> Self {
+    pub fn new_texture_descriptor(generation: usize, id: usize) -> Self {
         Self {
-            internal_id,
+            generation,
             kind: GLObjectDescriptorKind::Texture(id),
         }
     }
+
+    pub fn new_framebuffer_descriptor(generation: usize, id: usize) -> Self {
+        Self {
+            generation,
+            kind: GLObjectDescriptorKind::Framebuffer(id),
+        }
+    }
+
+    pub fn new_uniform_buffer_descriptor(generation: usize, id: usize) -> Self {
+        Self {
+            generation,
+            kind: GLObjectDescriptorKind::UniformBuffer(id),
+        }
+    }
 }
 
 impl Debug for GLObjectDescriptor {
@@ -0,0 +1,212 @@
+use super::{GLObject, GLObjectDescriptor, GLObjectDescriptorKind};
+use crate::{
+    error::{Error, Result},
+    framebuffer::Framebuffer,
+    shader::program::Program,
+    texture::Texture,
+    uniform::UniformBuffer,
+    vertex::VertexObject,
+};
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// A [GLObjectDescriptor] that's statically known to refer to an object of type `T`, so passing
+/// e.g. a texture where a program is expected is a compile error rather than a runtime
+/// [Error::UnexpectedObjectDescriptorKind]. Use [Handle::descriptor] and [Handle::try_from] to
+/// go back and forth between a handle and its untyped descriptor.
+pub struct Handle<T> {
+    desc: GLObjectDescriptor,
+    kind: PhantomData<fn() -> T>,
+}
+
+/// Types that can be stored in the [GLObjectRegistry](super::GLObjectRegistry).
+pub trait RegistryObject: Sized + 'static {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool;
+
+    fn from_object(obj: &GLObject) -> Option<&Self>;
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self>;
+
+    fn into_object(self) -> GLObject;
+}
+
+impl<T> Handle<T> {
+    pub(super) fn new(desc: GLObjectDescriptor) -> Self {
+        Self {
+            desc,
+            kind: PhantomData,
+        }
+    }
+
+    pub fn descriptor(&self) -> GLObjectDescriptor {
+        self.desc
+    }
+}
+
+impl<T: RegistryObject> TryFrom<GLObjectDescriptor> for Handle<T> {
+    type Error = Error;
+
+    fn try_from(desc: GLObjectDescriptor) -> Result<Self> {
+        if !T::matches_kind(desc.kind) {
+            return Err(Error::UnexpectedObjectDescriptorKind);
+        }
+        Ok(Self::new(desc))
+    }
+}
+
+impl<T> From<Handle<T>> for GLObjectDescriptor {
+    fn from(handle: Handle<T>) -> Self {
+        handle.desc
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.desc == other.desc
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.desc.hash(state);
+    }
+}
+
+impl<T> Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <GLObjectDescriptor as Debug>::fmt(&self.desc, f)
+    }
+}
+
+impl RegistryObject for VertexObject {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool {
+        matches!(kind, GLObjectDescriptorKind::VertexObject(_))
+    }
+
+    fn from_object(obj: &GLObject) -> Option<&Self> {
+        match obj {
+            GLObject::VertexObject(vertex_object) => Some(vertex_object),
+            _ => None,
+        }
+    }
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self> {
+        match obj {
+            GLObject::VertexObject(vertex_object) => Some(vertex_object),
+            _ => None,
+        }
+    }
+
+    fn into_object(self) -> GLObject {
+        GLObject::VertexObject(self)
+    }
+}
+
+impl RegistryObject for Program {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool {
+        matches!(kind, GLObjectDescriptorKind::Program(_))
+    }
+
+    fn from_object(obj: &GLObject) -> Option<&Self> {
+        match obj {
+            GLObject::Program(program) => Some(program),
+            _ => None,
+        }
+    }
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self> {
+        match obj {
+            GLObject::Program(program) => Some(program),
+            _ => None,
+        }
+    }
+
+    fn into_object(self) -> GLObject {
+        GLObject::Program(self)
+    }
+}
+
+impl RegistryObject for Texture {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool {
+        matches!(kind, GLObjectDescriptorKind::Texture(_))
+    }
+
+    fn from_object(obj: &GLObject) -> Option<&Self> {
+        match obj {
+            GLObject::Texture(texture) => Some(texture),
+            _ => None,
+        }
+    }
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self> {
+        match obj {
+            GLObject::Texture(texture) => Some(texture),
+            _ => None,
+        }
+    }
+
+    fn into_object(self) -> GLObject {
+        GLObject::Texture(self)
+    }
+}
+
+impl RegistryObject for Framebuffer {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool {
+        matches!(kind, GLObjectDescriptorKind::Framebuffer(_))
+    }
+
+    fn from_object(obj: &GLObject) -> Option<&Self> {
+        match obj {
+            GLObject::Framebuffer(framebuffer) => Some(framebuffer),
+            _ => None,
+        }
+    }
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self> {
+        match obj {
+            GLObject::Framebuffer(framebuffer) => Some(framebuffer),
+            _ => None,
+        }
+    }
+
+    fn into_object(self) -> GLObject {
+        GLObject::Framebuffer(self)
+    }
+}
+
+impl RegistryObject for UniformBuffer {
+    fn matches_kind(kind: GLObjectDescriptorKind) -> bool {
+        matches!(kind, GLObjectDescriptorKind::UniformBuffer(_))
+    }
+
+    fn from_object(obj: &GLObject) -> Option<&Self> {
+        match obj {
+            GLObject::UniformBuffer(uniform_buffer) => Some(uniform_buffer),
+            _ => None,
+        }
+    }
+
+    fn from_object_mut(obj: &mut GLObject) -> Option<&mut Self> {
+        match obj {
+            GLObject::UniformBuffer(uniform_buffer) => Some(uniform_buffer),
+            _ => None,
+        }
+    }
+
+    fn into_object(self) -> GLObject {
+        GLObject::UniformBuffer(self)
+    }
+}
@@ -1,26 +1,52 @@
 use crate::{
     error::{Error, Result},
+    framebuffer::Framebuffer,
     shader::program::Program,
     texture::Texture,
+    uniform::UniformBuffer,
     vertex::VertexObject,
 };
-use std::{collections::HashSet, fmt::Debug};
+use gl::types::GLuint;
+use std::{
+    fmt::{Debug, Write},
+    mem,
+    path::Path,
+};
 
 pub mod descriptor;
 pub use descriptor::{GLObjectDescriptor, GLObjectDescriptorKind};
 
+pub mod handle;
+pub use handle::{Handle, RegistryObject};
+
+pub mod owned;
+use owned::GarbageQueue;
+pub use owned::OwnedHandle;
+
+/// Objects are stored in slots that get reused once their object is removed. Every slot has a
+/// generation that's bumped on removal and recorded in each [GLObjectDescriptor] handed out, so a
+/// descriptor to a removed object can never resolve to whatever object reuses its slot. Vacated
+/// slots are kept in a free list so registering an object never has to search for one.
 #[derive(Debug)]
 pub struct GLObjectRegistry {
-    objects: Vec<Option<GLObject>>,
+    objects: Vec<Slot>,
+    free_slots: Vec<usize>,
     capacity_increment: usize,
-    current_internal_id: usize,
-    invalidated_ids: HashSet<usize>,
+    garbage: GarbageQueue,
+}
+
+#[derive(Debug)]
+struct Slot {
+    generation: usize,
+    object: Option<GLObject>,
 }
 
 pub enum GLObject {
     VertexObject(VertexObject),
     Program(Program),
     Texture(Texture),
+    Framebuffer(Framebuffer),
+    UniformBuffer(UniformBuffer),
 }
 
 impl Default for GLObjectRegistry {
@@ -33,38 +59,44 @@ impl GLObjectRegistry {
     pub fn new(object_capacity: usize, capacity_increment: usize) -> Self {
         Self {
             capacity_increment,
-            current_internal_id: 0,
             objects: Vec::with_capacity(object_capacity),
-            invalidated_ids: HashSet::new(),
+            free_slots: Vec::new(),
+            garbage: GarbageQueue::default(),
         }
     }
 
+    pub fn register<T: RegistryObject>(&mut self, obj: T) -> Handle<T> {
+        Handle::new(self.register_object(obj.into_object()))
+    }
+
+    /// Same as [GLObjectRegistry::register] but the object is deleted automatically once the last
+    /// clone of the returned [OwnedHandle] is dropped. See [OwnedHandle].
+    pub fn register_owned<T: RegistryObject>(&mut self, obj: T) -> OwnedHandle<T> {
+        let handle = self.register(obj);
+        self.own(handle)
+    }
+
+    /// Hands ownership of an already registered object over to a reference-counted handle. This
+    /// should be done at most once per object.
+    pub fn own<T: RegistryObject>(&self, handle: Handle<T>) -> OwnedHandle<T> {
+        OwnedHandle::new(handle, GarbageQueue::clone(&self.garbage))
+    }
+
     pub fn register_object(&mut self, obj: GLObject) -> GLObjectDescriptor {
-        let desc = {
-            if self.objects.len() < self.objects.capacity() {
-                let obj_desc = self.make_descriptor(&obj, self.objects.len());
-                self.objects.push(Some(obj));
+        let desc = match self.free_slots.pop() {
+            Some(idx) => {
+                let slot = &mut self.objects[idx];
+                let obj_desc = Self::make_descriptor(&obj, idx, slot.generation);
+                slot.object = Some(obj);
                 obj_desc
-            } else {
-                match self
-                    .objects
-                    .iter()
-                    .enumerate()
-                    .find(|(_, obj)| obj.is_none())
-                {
-                    Some((idx, _)) => {
-                        let obj_desc = self.make_descriptor(&obj, idx);
-                        let slot = &mut self.objects[idx];
-                        *slot = Some(obj);
-                        obj_desc
-                    }
-                    None => {
-                        let obj_desc = self.make_descriptor(&obj, self.objects.len());
-                        self.objects.reserve(self.capacity_increment);
-                        self.objects.push(Some(obj));
-                        obj_desc
-                    }
+            }
+            None => {
+                if self.objects.len() == self.objects.capacity() {
+                    self.objects.reserve(self.capacity_increment);
                 }
+                let obj_desc = Self::make_descriptor(&obj, self.objects.len(), 0);
+                self.objects.push(Slot::new(obj));
+                obj_desc
             }
         };
         let _ = self.get_object(desc).map(|ob| {
@@ -73,60 +105,163 @@ impl GLObjectRegistry {
         desc
     }
 
-    pub fn remove_object(&mut self, obj_desc: GLObjectDescriptor) -> Option<GLObject> {
-        if !self.descriptor_is_valid(obj_desc) {
-            return None;
-        }
-        let idx = Self::idx_from_descriptor(obj_desc);
-        let obj = &mut self.objects[idx];
-        obj.take()
+    /// Removes the object from the registry, invalidating `obj_desc` and every copy of it. Dropping
+    /// the returned object deletes the underlying OpenGL object.
+    pub fn remove_object(&mut self, obj_desc: impl Into<GLObjectDescriptor>) -> Option<GLObject> {
+        let obj_desc = obj_desc.into();
+        let slot = self.get_slot_mut(obj_desc).ok()?;
+        let obj = slot.object.take()?;
+        slot.generation += 1;
+        self.free_slots.push(Self::idx_from_descriptor(obj_desc));
+        log::debug!("removed object: object_storage_id={obj_desc:?} -> {obj:?}");
+        Some(obj)
     }
 
     pub fn get_object(&self, obj_desc: GLObjectDescriptor) -> Result<&GLObject> {
-        if !self.descriptor_is_valid(obj_desc) {
+        let idx = Self::idx_from_descriptor(obj_desc);
+        let slot = self
+            .objects
+            .get(idx)
+            .ok_or(Error::ObjectNotFound(obj_desc))?;
+        if slot.generation != obj_desc.generation {
             return Err(Error::InvalidObjectDescriptor);
         }
-        let idx = Self::idx_from_descriptor(obj_desc);
-        self.objects[idx]
-            .as_ref()
-            .ok_or(Error::ObjectNotFound(obj_desc))
+        slot.object.as_ref().ok_or(Error::ObjectNotFound(obj_desc))
     }
 
-    pub fn get_vertex_object(&self, obj_desc: GLObjectDescriptor) -> Result<&VertexObject> {
-        let GLObject::VertexObject(vertex_object) = self.get_object(obj_desc)? else {
-            return Err(Error::UnexpectedObjectDescriptorKind);
-        };
-        Ok(vertex_object)
+    pub fn get<T: RegistryObject>(&self, handle: Handle<T>) -> Result<&T> {
+        let obj = self.get_object(handle.descriptor())?;
+        T::from_object(obj).ok_or(Error::UnexpectedObjectDescriptorKind)
     }
 
-    pub fn get_program(&self, obj_desc: GLObjectDescriptor) -> Result<&Program> {
-        let GLObject::Program(program) = self.get_object(obj_desc)? else {
-            return Err(Error::UnexpectedObjectDescriptorKind);
-        };
-        Ok(program)
+    pub(crate) fn get_mut<T: RegistryObject>(&mut self, handle: Handle<T>) -> Result<&mut T> {
+        let obj = self
+            .get_slot_mut(handle.descriptor())?
+            .object
+            .as_mut()
+            .ok_or(Error::ObjectNotFound(handle.descriptor()))?;
+        T::from_object_mut(obj).ok_or(Error::UnexpectedObjectDescriptorKind)
     }
 
-    pub fn get_texture(&self, obj_desc: GLObjectDescriptor) -> Result<&Texture> {
-        let GLObject::Texture(texture) = self.get_object(obj_desc)? else {
-            return Err(Error::UnexpectedObjectDescriptorKind);
-        };
-        Ok(texture)
+    /// Swaps the object behind `handle` for `obj`, keeping `handle` and every copy of it valid.
+    /// Returns the previous object.
+    pub(crate) fn replace<T: RegistryObject>(&mut self, handle: Handle<T>, obj: T) -> Result<T> {
+        let slot = self.get_mut(handle)?;
+        Ok(mem::replace(slot, obj))
     }
 
-    fn make_descriptor(&mut self, obj: &GLObject, idx: usize) -> GLObjectDescriptor {
-        let desc = match obj {
+    pub fn get_vertex_object(&self, handle: Handle<VertexObject>) -> Result<&VertexObject> {
+        self.get(handle)
+    }
+
+    pub fn get_program(&self, handle: Handle<Program>) -> Result<&Program> {
+        self.get(handle)
+    }
+
+    pub fn get_texture(&self, handle: Handle<Texture>) -> Result<&Texture> {
+        self.get(handle)
+    }
+
+    pub fn get_framebuffer(&self, handle: Handle<Framebuffer>) -> Result<&Framebuffer> {
+        self.get(handle)
+    }
+
+    pub fn get_uniform_buffer(&self, handle: Handle<UniformBuffer>) -> Result<&UniformBuffer> {
+        self.get(handle)
+    }
+
+    /// Iterates over every object currently in the registry along with its descriptor.
+    pub fn iter_objects(&self) -> impl Iterator<Item = (GLObjectDescriptor, &GLObject)> {
+        self.objects.iter().enumerate().filter_map(|(idx, slot)| {
+            let obj = slot.object.as_ref()?;
+            Some((Self::make_descriptor(obj, idx, slot.generation), obj))
+        })
+    }
+
+    /// Iterates over every object of type `T` currently in the registry.
+    pub fn iter<T: RegistryObject>(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.iter_objects()
+            .filter_map(|(desc, obj)| Some((Handle::new(desc), T::from_object(obj)?)))
+    }
+
+    /// Number of objects of type `T` currently in the registry.
+    pub fn count<T: RegistryObject>(&self) -> usize {
+        self.iter::<T>().count()
+    }
+
+    /// Total number of objects currently in the registry.
+    pub fn len(&self) -> usize {
+        self.objects.len() - self.free_slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Human readable listing of every object in the registry with its OpenGL name and the files
+    /// it was created from, if any. Intended for debugging leaks.
+    pub fn debug_dump(&self) -> String {
+        let mut dump = format!(
+            "{} objects: {} vertex objects, {} programs, {} textures, {} framebuffers, {} uniform buffers\n",
+            self.len(),
+            self.count::<VertexObject>(),
+            self.count::<Program>(),
+            self.count::<Texture>(),
+            self.count::<Framebuffer>(),
+            self.count::<UniformBuffer>(),
+        );
+        for (desc, obj) in self.iter_objects() {
+            let sources = obj.src_paths();
+            let _ = write!(
+                dump,
+                "  {desc:?} generation={} gl_object_id={}",
+                desc.generation,
+                obj.gl_object_id()
+            );
+            if !sources.is_empty() {
+                let sources = sources
+                    .iter()
+                    .map(|p| p.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let _ = write!(dump, " src='{sources}'");
+            }
+            dump.push('\n');
+        }
+        dump
+    }
+
+    /// Descriptors of objects whose [OwnedHandle]s have all been dropped.
+    pub(crate) fn take_garbage(&self) -> Vec<GLObjectDescriptor> {
+        self.garbage.take()
+    }
+
+    fn get_slot_mut(&mut self, obj_desc: GLObjectDescriptor) -> Result<&mut Slot> {
+        let idx = Self::idx_from_descriptor(obj_desc);
+        let slot = self
+            .objects
+            .get_mut(idx)
+            .ok_or(Error::ObjectNotFound(obj_desc))?;
+        if slot.generation != obj_desc.generation {
+            return Err(Error::InvalidObjectDescriptor);
+        }
+        Ok(slot)
+    }
+
+    fn make_descriptor(obj: &GLObject, idx: usize, generation: usize) -> GLObjectDescriptor {
+        match obj {
             GLObject::VertexObject(_) => {
-                GLObjectDescriptor::new_vertex_object_descriptor(self.current_internal_id, idx)
+                GLObjectDescriptor::new_vertex_object_descriptor(generation, idx)
             }
-            GLObject::Program(_) => {
-                GLObjectDescriptor::new_program_descriptor(self.current_internal_id, idx)
+            GLObject::Program(_) => GLObjectDescriptor::new_program_descriptor(generation, idx),
+            GLObject::Texture(_) => GLObjectDescriptor::new_texture_descriptor(generation, idx),
+            GLObject::Framebuffer(_) => {
+                GLObjectDescriptor::new_framebuffer_descriptor(generation, idx)
             }
-            GLObject::Texture(_) => {
-                GLObjectDescriptor::new_texture_descriptor(self.current_internal_id, idx)
+            GLObject::UniformBuffer(_) => {
+                GLObjectDescriptor::new_uniform_buffer_descriptor(generation, idx)
             }
-        };
-        self.current_internal_id += 1;
-        desc
+        }
     }
 
     fn idx_from_descriptor(obj_desc: GLObjectDescriptor) -> usize {
@@ -134,11 +269,40 @@ impl GLObjectRegistry {
             GLObjectDescriptorKind::VertexObject(idx) => idx,
             GLObjectDescriptorKind::Program(idx) => idx,
             GLObjectDescriptorKind::Texture(idx) => idx,
+            GLObjectDescriptorKind::Framebuffer(idx) => idx,
+            GLObjectDescriptorKind::UniformBuffer(idx) => idx,
         }
     }
+}
 
-    fn descriptor_is_valid(&self, obj_desc: GLObjectDescriptor) -> bool {
-        !self.invalidated_ids.contains(&obj_desc.internal_id)
+impl GLObject {
+    /// The name OpenGL knows this object by; for vertex objects this is the vertex array object.
+    pub fn gl_object_id(&self) -> GLuint {
+        match self {
+            Self::VertexObject(o) => o.vertex_array_object,
+            Self::Program(o) => o.gl_object_id,
+            Self::Texture(o) => o.gl_object_id,
+            Self::Framebuffer(o) => o.gl_object_id,
+            Self::UniformBuffer(o) => o.gl_object_id,
+        }
+    }
+
+    /// Files this object was created from.
+    pub fn src_paths(&self) -> Vec<&Path> {
+        match self {
+            Self::Program(o) => o.shader_src_paths(),
+            Self::Texture(o) if !o.src.as_os_str().is_empty() => vec![o.src.as_path()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Slot {
+    fn new(object: GLObject) -> Self {
+        Self {
+            generation: 0,
+            object: Some(object),
+        }
     }
 }
 
@@ -148,6 +312,8 @@ impl Debug for GLObject {
             Self::VertexObject(o) => <VertexObject as Debug>::fmt(o, f),
             Self::Texture(o) => <Texture as Debug>::fmt(o, f),
             Self::Program(o) => <Program as Debug>::fmt(o, f),
+            Self::Framebuffer(o) => <Framebuffer as Debug>::fmt(o, f),
+            Self::UniformBuffer(o) => <UniformBuffer as Debug>::fmt(o, f),
         }
     }
 }
@@ -0,0 +1,88 @@
+use super::{GLObjectDescriptor, Handle};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+/// Descriptors of objects whose last [OwnedHandle] has been dropped and that are waiting to be
+/// removed from the registry.
+pub(crate) type GarbageQueue = Rc<RefCell<Vec<GLObjectDescriptor>>>;
+
+/// A reference-counted [Handle]. Once the last clone of an [OwnedHandle] is dropped, the object
+/// it refers to is queued for deletion, which happens the next time
+/// [GLContext::collect_garbage](crate::context::GLContext::collect_garbage) is called. Deletion
+/// is deferred so that OpenGL objects are only ever deleted at a well defined point on the thread
+/// that owns the context.
+///
+/// Plain [Handle]s obtained via [OwnedHandle::handle] don't keep the object alive.
+pub struct OwnedHandle<T> {
+    handle: Handle<T>,
+    owner: Rc<Owner>,
+}
+
+/// Shared by all clones of an [OwnedHandle]; queues the object for deletion when dropped.
+pub(crate) struct Owner {
+    desc: GLObjectDescriptor,
+    garbage: GarbageQueue,
+}
+
+impl<T> OwnedHandle<T> {
+    pub(super) fn new(handle: Handle<T>, garbage: GarbageQueue) -> Self {
+        let owner = Owner {
+            garbage,
+            desc: handle.descriptor(),
+        };
+        Self {
+            handle,
+            owner: Rc::new(owner),
+        }
+    }
+
+    pub fn handle(&self) -> Handle<T> {
+        self.handle
+    }
+
+    /// Type-erased reference that keeps the object alive.
+    pub(crate) fn owner(&self) -> Rc<Owner> {
+        Rc::clone(&self.owner)
+    }
+}
+
+impl<T> From<&OwnedHandle<T>> for Handle<T> {
+    fn from(owned: &OwnedHandle<T>) -> Self {
+        owned.handle
+    }
+}
+
+impl<T> Clone for OwnedHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle,
+            owner: Rc::clone(&self.owner),
+        }
+    }
+}
+
+impl<T> Debug for OwnedHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "OwnedHandle {{ {:?}, strong_count={} }}",
+            self.handle,
+            Rc::strong_count(&self.owner)
+        )
+    }
+}
+
+impl Debug for Owner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <GLObjectDescriptor as Debug>::fmt(&self.desc, f)
+    }
+}
+
+impl Drop for Owner {
+    fn drop(&mut self) {
+        log::debug!(
+            "queued object for deletion: object_storage_id={:?}",
+            self.desc
+        );
+        self.garbage.borrow_mut().push(self.desc);
+    }
+}
@@ -1,24 +1,73 @@
 use super::try_into;
-use crate::{Error, Result};
+use crate::{internal_utils::gl_version, Error, Result};
 use std::{
     convert::AsRef,
     ffi::{CString, OsStr},
-    fs, mem,
+    fmt::{self, Display},
+    fs,
     path::{Path, PathBuf},
     ptr,
 };
 
+pub mod preprocessor;
+pub use preprocessor::{
+    FileResolver, IncludeResolver, MapResolver, PreprocessedSource, Preprocessor,
+};
+
 pub mod program;
 pub use program::Program;
 
+pub mod reflection;
+pub use reflection::ActiveVariable;
+
 pub struct Shader {
     pub gl_object_id: gl::types::GLuint,
-    pub src: PathBuf,
+    pub origin: ShaderOrigin,
+    pub kind: ShaderType,
+    /// Files pulled in through `#include` when the shader was loaded from a file.
+    pub includes: Vec<PathBuf>,
 }
 
+/// Where the source of a [Shader] came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderOrigin {
+    File(PathBuf),
+    /// Source provided in memory, labeled with the name it was given.
+    Source(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ShaderType {
     Vertex,
     Fragment,
+    Geometry,
+    TessControl,
+    TessEvaluation,
+    /// Can't be combined with any other stage. Requires OpenGL 4.3.
+    Compute,
+}
+
+impl ShaderType {
+    /// Oldest OpenGL version, as `(major, minor)`, that supports this stage.
+    pub fn min_gl_version(self) -> (gl::types::GLint, gl::types::GLint) {
+        match self {
+            ShaderType::Vertex | ShaderType::Fragment => (2, 0),
+            ShaderType::Geometry => (3, 2),
+            ShaderType::TessControl | ShaderType::TessEvaluation => (4, 0),
+            ShaderType::Compute => (4, 3),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ShaderType::Vertex => "vertex shader",
+            ShaderType::Fragment => "fragment shader",
+            ShaderType::Geometry => "geometry shader",
+            ShaderType::TessControl => "tessellation control shader",
+            ShaderType::TessEvaluation => "tessellation evaluation shader",
+            ShaderType::Compute => "compute shader",
+        }
+    }
 }
 
 impl From<ShaderType> for gl::types::GLenum {
@@ -26,11 +75,17 @@ impl From<ShaderType> for gl::types::GLenum {
         match shader_type {
             ShaderType::Vertex => gl::VERTEX_SHADER,
             ShaderType::Fragment => gl::FRAGMENT_SHADER,
+            ShaderType::Geometry => gl::GEOMETRY_SHADER,
+            ShaderType::TessControl => gl::TESS_CONTROL_SHADER,
+            ShaderType::TessEvaluation => gl::TESS_EVALUATION_SHADER,
+            ShaderType::Compute => gl::COMPUTE_SHADER,
         }
     }
 }
 
 impl Shader {
+    /// Loads and compiles the shader at `src`. `#include`s are resolved relative to the directory
+    /// of the including file; see [Preprocessor].
     pub fn new<P: AsRef<Path>>(src: P, typ: ShaderType) -> Result<Self> {
         let shader_path = src.as_ref();
         let shader_src = fs::read_to_string(src.as_ref()).map_err(Error::boxed)?;
@@ -40,15 +95,59 @@ impl Shader {
             .ok_or(Error::Misc(
                 "expected shader to have a file name".to_string(),
             ))?;
-        let gl_object_id = Self::compile_src(&file_name, &shader_src, typ)?;
+        let preprocessed = Preprocessor::new(&FileResolver)
+            .process(&shader_path.to_string_lossy(), &shader_src)?;
+        let gl_object_id = Self::compile_src(&file_name, &preprocessed, typ)?;
+        Ok(Self {
+            gl_object_id,
+            origin: ShaderOrigin::File(shader_path.to_path_buf()),
+            kind: typ,
+            includes: preprocessed
+                .includes
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+        })
+    }
+
+    /// Compiles `src` directly e.g. shaders embedded with [include_str] or generated at runtime.
+    /// `name` is used in place of a file name in errors and debug output. Any `#include` is an
+    /// error; use [Shader::from_source_with_resolver] to provide included sources.
+    pub fn from_source(name: &str, src: &str, typ: ShaderType) -> Result<Self> {
+        Self::from_source_with_resolver(name, src, typ, &MapResolver::new())
+    }
+
+    /// Same as [Shader::from_source] but `#include`s are looked up using `resolver`.
+    pub fn from_source_with_resolver(
+        name: &str,
+        src: &str,
+        typ: ShaderType,
+        resolver: &dyn IncludeResolver,
+    ) -> Result<Self> {
+        let preprocessed = Preprocessor::new(resolver).process(name, src)?;
+        let gl_object_id = Self::compile_src(name, &preprocessed, typ)?;
         Ok(Self {
             gl_object_id,
-            src: shader_path.to_path_buf(),
+            origin: ShaderOrigin::Source(name.to_string()),
+            kind: typ,
+            includes: Vec::new(),
         })
     }
 
-    fn compile_src(file_name: &str, src: &str, typ: ShaderType) -> Result<gl::types::GLuint> {
-        let shader_src = CString::new(src).map_err(Error::boxed)?;
+    fn compile_src(
+        file_name: &str,
+        src: &PreprocessedSource,
+        typ: ShaderType,
+    ) -> Result<gl::types::GLuint> {
+        let shader_src = CString::new(src.src.as_str()).map_err(Error::boxed)?;
+        let (required, current) = (typ.min_gl_version(), gl_version());
+        if current < required {
+            return Err(Error::UnsupportedGLVersion {
+                feature: typ.name(),
+                required,
+                current,
+            });
+        }
 
         unsafe {
             let shader = gl::CreateShader(typ.into());
@@ -59,14 +158,14 @@ impl Shader {
             gl::GetShaderiv(shader, gl::COMPILE_STATUS, &mut success);
 
             if success == 0 {
-                let mut log: [u8; 512] = [0; 512];
-                gl::GetShaderInfoLog(
-                    shader,
-                    try_into!(mem::size_of::<u8>() * log.len()),
-                    ptr::null_mut(),
-                    log.as_mut_ptr() as *mut i8,
-                );
-                let reason = String::from_utf8_lossy(&log);
+                let mut log_len = 0;
+                gl::GetShaderiv(shader, gl::INFO_LOG_LENGTH, &mut log_len);
+                let mut log = vec![0_u8; try_into!(log_len)];
+                let mut written = 0;
+                gl::GetShaderInfoLog(shader, log_len, &mut written, log.as_mut_ptr() as *mut i8);
+                log.truncate(try_into!(written));
+                gl::DeleteShader(shader);
+                let reason = src.remap_log(&String::from_utf8_lossy(&log));
 
                 return Err(Error::ShaderCompile {
                     shader_name: String::from(file_name),
@@ -77,3 +176,21 @@ impl Shader {
         }
     }
 }
+
+impl ShaderOrigin {
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::File(path) => Some(path),
+            Self::Source(_) => None,
+        }
+    }
+}
+
+impl Display for ShaderOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Source(name) => write!(f, "<{name}>"),
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use crate::error::{Error, Result};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Looks up the source of `#include`d files.
+pub trait IncludeResolver {
+    /// Resolves `name` as written in an `#include` directive inside the source identified by
+    /// `includer`. Returns an identifier that's unique per file, used for cycle detection,
+    /// `#pragma once` and error messages, along with the file's source.
+    fn resolve(&self, name: &str, includer: &str) -> Result<ResolvedInclude>;
+}
+
+#[derive(Debug)]
+pub struct ResolvedInclude {
+    pub id: String,
+    pub src: String,
+}
+
+/// Resolves includes relative to the directory of the file containing the directive.
+#[derive(Debug, Default)]
+pub struct FileResolver;
+
+/// Resolves includes from sources kept in memory e.g. embedded with [include_str]. Names are
+/// matched exactly.
+#[derive(Debug, Default)]
+pub struct MapResolver {
+    sources: HashMap<String, String>,
+}
+
+/// Expands `#include "name"` and `#include <name>` directives. An included file containing
+/// `#pragma once` is only expanded the first time it's included; files that include themselves,
+/// directly or not, are an error.
+pub struct Preprocessor<'a> {
+    resolver: &'a dyn IncludeResolver,
+}
+
+/// Fully expanded shader source.
+#[derive(Debug)]
+pub struct PreprocessedSource {
+    pub src: String,
+    /// Identifiers of all files that were included.
+    pub includes: Vec<String>,
+    line_map: Vec<(usize, usize)>,
+    files: Vec<String>,
+}
+
+struct ExpansionState {
+    out: PreprocessedSource,
+    stack: Vec<String>,
+    once: HashSet<String>,
+}
+
+impl IncludeResolver for FileResolver {
+    fn resolve(&self, name: &str, includer: &str) -> Result<ResolvedInclude> {
+        let path = Path::new(includer)
+            .parent()
+            .map_or_else(|| PathBuf::from(name), |dir| dir.join(name));
+        let src = fs::read_to_string(&path)
+            .map_err(|e| Error::ShaderInclude(format!("failed to read {}: {e}", path.display())))?;
+        Ok(ResolvedInclude {
+            src,
+            id: path.to_string_lossy().into_owned(),
+        })
+    }
+}
+
+impl MapResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(mut self, name: &str, src: &str) -> Self {
+        self.sources.insert(name.to_string(), src.to_string());
+        self
+    }
+}
+
+impl IncludeResolver for MapResolver {
+    fn resolve(&self, name: &str, _includer: &str) -> Result<ResolvedInclude> {
+        let src = self
+            .sources
+            .get(name)
+            .ok_or_else(|| Error::ShaderInclude(format!("no source registered for '{name}'")))?;
+        Ok(ResolvedInclude {
+            id: name.to_string(),
+            src: src.clone(),
+        })
+    }
+}
+
+impl<'a> Preprocessor<'a> {
+    pub fn new(resolver: &'a dyn IncludeResolver) -> Self {
+        Self { resolver }
+    }
+
+    /// `name` identifies `src` to the resolver and in the line map; for files this should be
+    /// the path.
+    pub fn process(&self, name: &str, src: &str) -> Result<PreprocessedSource> {
+        let mut state = ExpansionState {
+            out: PreprocessedSource {
+                src: String::with_capacity(src.len()),
+                includes: Vec::new(),
+                line_map: Vec::new(),
+                files: Vec::new(),
+            },
+            stack: Vec::new(),
+            once: HashSet::new(),
+        };
+        self.expand(name, src, &mut state)?;
+        Ok(state.out)
+    }
+
+    fn expand(&self, id: &str, src: &str, state: &mut ExpansionState) -> Result<()> {
+        if state.once.contains(id) {
+            return Ok(());
+        }
+        state.stack.push(id.to_string());
+        let file_idx = state.out.file_index(id);
+
+        for (i, line) in src.lines().enumerate() {
+            let directive = line.trim_start();
+
+            if is_pragma_once(directive) {
+                state.once.insert(id.to_string());
+                continue;
+            }
+
+            let Some(rest) = directive.strip_prefix("#include") else {
+                state.out.push_line(line, file_idx, i + 1);
+                continue;
+            };
+
+            let name = parse_include_name(rest).ok_or_else(|| {
+                Error::ShaderInclude(format!("{id}:{}: malformed #include directive", i + 1))
+            })?;
+            let include = self.resolver.resolve(name, id).map_err(|e| match e {
+                Error::ShaderInclude(reason) => {
+                    Error::ShaderInclude(format!("{id}:{}: {reason}", i + 1))
+                }
+                e => e,
+            })?;
+
+            if state.stack.contains(&include.id) {
+                return Err(Error::ShaderInclude(format!(
+                    "{id}:{}: include cycle: {} -> {}",
+                    i + 1,
+                    state.stack.join(" -> "),
+                    include.id
+                )));
+            }
+            if !state.out.includes.contains(&include.id) {
+                state.out.includes.push(include.id.clone());
+            }
+            self.expand(&include.id, &include.src, state)?;
+        }
+
+        state.stack.pop();
+        Ok(())
+    }
+}
+
+impl PreprocessedSource {
+    /// Maps a line of the expanded source, starting at 1, back to the file and line it came from.
+    pub fn original_location(&self, line: usize) -> Option<(&str, usize)> {
+        let (file_idx, original_line) = *self.line_map.get(line.checked_sub(1)?)?;
+        Some((&self.files[file_idx], original_line))
+    }
+
+    /// Rewrites the line numbers in a compile log so that they refer to the original files.
+    /// Understands the `0:LINE` and `0(LINE)` formats used by most drivers.
+    pub fn remap_log(&self, log: &str) -> String {
+        log.lines()
+            .map(|line| self.remap_log_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn remap_log_line(&self, line: &str) -> String {
+        for (open, close) in [("0:", None), ("0(", Some(')'))] {
+            let Some(start) = line.find(open) else {
+                continue;
+            };
+            if line[..start].ends_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+            let digits_start = start + open.len();
+            let digits_len = line[digits_start..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(line.len() - digits_start);
+            let digits_end = digits_start + digits_len;
+            let end = match close {
+                Some(c) if line[digits_end..].starts_with(c) => digits_end + c.len_utf8(),
+                Some(_) => continue,
+                None => digits_end,
+            };
+
+            let location = line[digits_start..digits_end]
+                .parse()
+                .ok()
+                .and_then(|n| self.original_location(n));
+            if let Some((file, original_line)) = location {
+                return format!("{}{file}:{original_line}{}", &line[..start], &line[end..]);
+            }
+        }
+        line.to_string()
+    }
+
+    fn file_index(&mut self, id: &str) -> usize {
+        self.files.iter().position(|f| f == id).unwrap_or_else(|| {
+            self.files.push(id.to_string());
+            self.files.len() - 1
+        })
+    }
+
+    fn push_line(&mut self, line: &str, file_idx: usize, original_line: usize) {
+        self.src.push_str(line);
+        self.src.push('\n');
+        self.line_map.push((file_idx, original_line));
+    }
+}
+
+fn is_pragma_once(directive: &str) -> bool {
+    directive
+        .strip_prefix('#')
+        .map(str::trim_start)
+        .and_then(|d| d.strip_prefix("pragma"))
+        .is_some_and(|d| d.split_whitespace().eq(["once"]))
+}
+
+fn parse_include_name(rest: &str) -> Option<&str> {
+    let rest = rest.trim();
+    let close = match rest.chars().next()? {
+        '"' => '"',
+        '<' => '>',
+        _ => return None,
+    };
+    let inner = &rest[1..];
+    let end = inner.find(close)?;
+    let trailing = inner[end + 1..].trim_start();
+    if !(trailing.is_empty() || trailing.starts_with("//")) {
+        return None;
+    }
+    Some(&inner[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(resolver: &MapResolver, src: &str) -> Result<PreprocessedSource> {
+        Preprocessor::new(resolver).process("main.glsl", src)
+    }
+
+    #[test]
+    fn include() {
+        let resolver = MapResolver::new()
+            .add("common.glsl", "float a;\nfloat b;")
+            .add("light.glsl", "#include \"common.glsl\"\nvec3 light;");
+        let out = process(
+            &resolver,
+            "#version 330 core\n#include <light.glsl>\nvoid main() {}",
+        )
+        .unwrap();
+        assert_eq!(
+            out.src,
+            "#version 330 core\nfloat a;\nfloat b;\nvec3 light;\nvoid main() {}\n"
+        );
+        assert_eq!(out.includes, ["light.glsl", "common.glsl"]);
+    }
+
+    #[test]
+    fn unresolved_and_malformed() {
+        let resolver = MapResolver::new();
+        let err = process(&resolver, "void f();\n#include \"missing.glsl\"").unwrap_err();
+        assert!(err.to_string().contains("main.glsl:2"));
+        assert!(process(&resolver, "#include missing.glsl").is_err());
+        assert!(process(&resolver, "#include \"a.glsl\" junk").is_err());
+    }
+
+    #[test]
+    fn cycle() {
+        let resolver = MapResolver::new()
+            .add("a.glsl", "#include \"b.glsl\"")
+            .add("b.glsl", "#include \"a.glsl\"");
+        let err = process(&resolver, "#include \"a.glsl\"").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("main.glsl -> a.glsl -> b.glsl -> a.glsl"));
+
+        let resolver = MapResolver::new().add("self.glsl", "#include \"self.glsl\"");
+        assert!(process(&resolver, "#include \"self.glsl\"").is_err());
+    }
+
+    #[test]
+    fn pragma_once() {
+        let resolver = MapResolver::new()
+            .add("once.glsl", "#pragma once\nfloat once;")
+            .add("twice.glsl", "float twice;");
+        let src = "#include \"once.glsl\"\n#include \"once.glsl\"\n#include \"twice.glsl\"\n#include \"twice.glsl\"";
+        let out = process(&resolver, src).unwrap();
+        assert_eq!(out.src, "float once;\nfloat twice;\nfloat twice;\n");
+        assert_eq!(out.includes, ["once.glsl", "twice.glsl"]);
+        assert!(is_pragma_once("#  pragma   once"));
+        assert!(!is_pragma_once("#pragma optimize(off)"));
+    }
+
+    #[test]
+    fn line_map() {
+        let resolver = MapResolver::new().add("inc.glsl", "#pragma once\nfloat a;\nfloat b;");
+        let out = process(
+            &resolver,
+            "#version 330 core\n#include \"inc.glsl\"\nvoid main() {}",
+        )
+        .unwrap();
+        assert_eq!(out.original_location(1), Some(("main.glsl", 1)));
+        assert_eq!(out.original_location(2), Some(("inc.glsl", 2)));
+        assert_eq!(out.original_location(3), Some(("inc.glsl", 3)));
+        assert_eq!(out.original_location(4), Some(("main.glsl", 3)));
+        assert_eq!(out.original_location(0), None);
+        assert_eq!(out.original_location(5), None);
+    }
+
+    #[test]
+    fn remap_log() {
+        let resolver = MapResolver::new().add("inc.glsl", "float a;\nfloat b;");
+        let out = process(
+            &resolver,
+            "#version 330 core\n#include \"inc.glsl\"\nvoid main() {}",
+        )
+        .unwrap();
+        let log = "ERROR: 0:3: 'b' : redefinition\n0(4) : error C0000: syntax error\n10:3 untouched\nERROR: 0:99: out of range";
+        assert_eq!(
+            out.remap_log(log),
+            "ERROR: inc.glsl:2: 'b' : redefinition\nmain.glsl:3 : error C0000: syntax error\n10:3 untouched\nERROR: 0:99: out of range"
+        );
+    }
+}
@@ -1,15 +1,41 @@
-use super::Shader;
+use super::{
+    reflection::{self, ActiveVariable},
+    Shader, ShaderOrigin, ShaderType,
+};
 use crate::{
     context::GLContext,
     error::{Error, Result},
-    object::{GLObject, GLObjectDescriptor},
+    object::Handle,
+};
+use gl::types::{GLint, GLuint};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::CString,
+    fmt::Debug,
+    fs, mem,
+    ops::Drop,
+    path::{Path, PathBuf},
+    ptr,
+    time::SystemTime,
 };
-use std::{fmt::Debug, mem, ops::Drop, path::PathBuf, ptr};
 
 #[derive(PartialEq, Eq)]
 pub struct Program {
     pub gl_object_id: gl::types::GLuint,
-    shader_src_paths: Vec<PathBuf>,
+    shader_origins: Vec<ShaderOrigin>,
+    shader_kinds: Vec<ShaderType>,
+    /// Shader files and their includes along with their modification time when last checked.
+    watched_files: Vec<(PathBuf, Option<SystemTime>)>,
+    attributes: Vec<ActiveVariable>,
+    uniforms: Vec<ActiveVariable>,
+    uniform_locations: RefCell<HashMap<String, GLint>>,
+    /// Id of the [Mesh](crate::mesh::Mesh) whose uniform values were last uploaded, if the
+    /// program's uniforms are known to hold nothing but those values.
+    last_uploader: Cell<Option<u64>>,
+    /// Uniform blocks and the binding points they were assigned, so they can be reassigned when
+    /// the program is reloaded.
+    block_bindings: Vec<(String, GLuint)>,
 }
 
 pub struct Linker {
@@ -23,6 +49,147 @@ impl Default for Linker {
     }
 }
 
+impl Program {
+    pub fn shader_origins(&self) -> &[ShaderOrigin] {
+        &self.shader_origins
+    }
+
+    /// Paths of the shaders that were loaded from files.
+    pub fn shader_src_paths(&self) -> Vec<&Path> {
+        self.shader_origins
+            .iter()
+            .filter_map(ShaderOrigin::path)
+            .collect()
+    }
+
+    /// Active vertex attributes as reported by the driver after linking. Attributes that don't
+    /// contribute to the output are optimized out and won't show up here.
+    pub fn active_attributes(&self) -> &[ActiveVariable] {
+        &self.attributes
+    }
+
+    /// Active uniforms, including those in uniform blocks, as reported by the driver after
+    /// linking. Uniforms that don't contribute to the output are optimized out and won't show up
+    /// here.
+    pub fn active_uniforms(&self) -> &[ActiveVariable] {
+        &self.uniforms
+    }
+
+    pub fn active_attribute(&self, name: &str) -> Option<&ActiveVariable> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    /// Array elements can be looked up with an index e.g. `lights[2]`, in which case the
+    /// variable describing the whole array is returned. Uniforms in uniform blocks can't be set
+    /// one by one and are skipped; they're only listed by [Program::active_uniforms].
+    pub fn active_uniform(&self, name: &str) -> Option<&ActiveVariable> {
+        self.find_uniform(name).filter(|u| u.location != -1)
+    }
+
+    /// Whether `name` is an active uniform that lives in a uniform block.
+    pub(crate) fn is_block_uniform(&self, name: &str) -> bool {
+        self.find_uniform(name).is_some_and(|u| u.location == -1)
+    }
+
+    fn find_uniform(&self, name: &str) -> Option<&ActiveVariable> {
+        if let Some(uniform) = self.uniforms.iter().find(|u| u.name == name) {
+            return Some(uniform);
+        }
+        let (array, idx) = name.strip_suffix(']')?.rsplit_once('[')?;
+        let idx: GLint = idx.parse().ok()?;
+        self.uniforms
+            .iter()
+            .find(|u| u.name == array && (0..u.size).contains(&idx))
+    }
+
+    /// Location of `uniform`, queried from the driver the first time it's requested and cached
+    /// afterwards. Unknown uniforms are cached as `-1`.
+    pub(crate) fn uniform_location(&self, uniform: &str) -> Result<GLint> {
+        if let Some(loc) = self.uniform_locations.borrow().get(uniform) {
+            return Ok(*loc);
+        }
+        let c_uniform = CString::new(uniform).map_err(Error::boxed)?;
+        let loc = unsafe { gl::GetUniformLocation(self.gl_object_id, c_uniform.as_ptr()) };
+        self.uniform_locations
+            .borrow_mut()
+            .insert(uniform.to_string(), loc);
+        Ok(loc)
+    }
+
+    pub(crate) fn last_uploader(&self) -> Option<u64> {
+        self.last_uploader.get()
+    }
+
+    pub(crate) fn set_last_uploader(&self, mesh_id: Option<u64>) {
+        self.last_uploader.set(mesh_id);
+    }
+
+    /// Assigns the uniform block named `block` to uniform buffer binding point `binding`.
+    pub(crate) fn bind_uniform_block(&mut self, block: &str, binding: GLuint) -> Result<()> {
+        let c_block = CString::new(block).map_err(Error::boxed)?;
+        unsafe {
+            let idx = gl::GetUniformBlockIndex(self.gl_object_id, c_block.as_ptr());
+            if idx == gl::INVALID_INDEX {
+                return Err(Error::UniformBlockNotFound(block.to_string()));
+            }
+            gl::UniformBlockBinding(self.gl_object_id, idx, binding);
+        }
+        match self.block_bindings.iter_mut().find(|(b, _)| b == block) {
+            Some((_, point)) => *point = binding,
+            None => self.block_bindings.push((block.to_string(), binding)),
+        }
+        Ok(())
+    }
+
+    pub(crate) fn take_block_bindings(&mut self) -> Vec<(String, GLuint)> {
+        mem::take(&mut self.block_bindings)
+    }
+
+    /// Whether any shader file, or any file it includes, was modified since the program was
+    /// linked or last checked by [GLContext::reload_modified_programs].
+    pub fn is_modified(&self) -> bool {
+        self.watched_files
+            .iter()
+            .any(|(path, mtime)| modified_time(path) != *mtime)
+    }
+
+    /// Compiles and links a fresh copy of this program from its shader files.
+    pub(crate) fn relink(&self) -> Result<Program> {
+        let mut shaders = Vec::with_capacity(self.shader_origins.len());
+        for (origin, kind) in self.shader_origins.iter().zip(&self.shader_kinds) {
+            let compiled = match origin {
+                ShaderOrigin::File(path) => Shader::new(path, *kind),
+                ShaderOrigin::Source(_) => Err(Error::Misc(format!(
+                    "can't reload {origin} because it wasn't loaded from a file"
+                ))),
+            };
+            match compiled {
+                Ok(shader) => shaders.push(shader),
+                Err(e) => {
+                    for shader in shaders {
+                        unsafe { gl::DeleteShader(shader.gl_object_id) };
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        shaders
+            .into_iter()
+            .fold(Linker::new(), Linker::attach_shader)
+            .link_impl()
+    }
+
+    pub fn is_compute(&self) -> bool {
+        self.shader_kinds.contains(&ShaderType::Compute)
+    }
+
+    pub(crate) fn refresh_modified_times(&mut self) {
+        for (path, mtime) in self.watched_files.iter_mut() {
+            *mtime = modified_time(path);
+        }
+    }
+}
+
 impl Linker {
     pub fn new() -> Self {
         let shaders = vec![];
@@ -35,13 +202,81 @@ impl Linker {
         self
     }
 
-    pub fn link(self, ctx: &mut GLContext) -> Result<GLObjectDescriptor> {
+    /// Compiles and links a vertex and fragment shader given as source strings. `name` labels
+    /// the shaders in errors and debug output.
+    pub fn link_sources(
+        ctx: &mut GLContext,
+        name: &str,
+        vertex_src: &str,
+        fragment_src: &str,
+    ) -> Result<Handle<Program>> {
+        let vertex = Shader::from_source(&format!("{name}.vert"), vertex_src, ShaderType::Vertex)?;
+        let fragment =
+            Shader::from_source(&format!("{name}.frag"), fragment_src, ShaderType::Fragment)?;
+        Self::new()
+            .attach_shader(vertex)
+            .attach_shader(fragment)
+            .link(ctx)
+    }
+
+    pub fn link(self, ctx: &mut GLContext) -> Result<Handle<Program>> {
+        let program = self.link_impl()?;
+        let handle = ctx.register(program);
+        Ok(handle)
+    }
+
+    /// Checks that the attached stages form a valid program: either a single compute stage, or a
+    /// vertex stage optionally followed by tessellation, geometry and fragment stages. A
+    /// tessellation control stage requires a tessellation evaluation stage.
+    fn validate_stages(&self) -> Result<()> {
+        let has = |kind| self.shaders.iter().any(|s| s.kind == kind);
+
+        if has(ShaderType::Compute) {
+            if self.shaders.iter().any(|s| s.kind != ShaderType::Compute) {
+                return Err(Error::InvalidProgramStages(
+                    "compute shaders can't be linked with other stages".to_string(),
+                ));
+            }
+            return Ok(());
+        }
+        if !has(ShaderType::Vertex) {
+            return Err(Error::InvalidProgramStages(
+                "program requires a vertex shader".to_string(),
+            ));
+        }
+        if has(ShaderType::TessControl) && !has(ShaderType::TessEvaluation) {
+            return Err(Error::InvalidProgramStages(
+                "tessellation control shader requires a tessellation evaluation shader".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn link_impl(self) -> Result<Program> {
+        if let Err(e) = self.validate_stages() {
+            unsafe {
+                for shader in self.shaders {
+                    gl::DeleteShader(shader.gl_object_id);
+                }
+                gl::DeleteProgram(self.program);
+            }
+            return Err(e);
+        }
+
         unsafe {
-            let mut shader_src_paths = Vec::with_capacity(self.shaders.len());
+            let mut shader_origins = Vec::with_capacity(self.shaders.len());
+            let mut shader_kinds = Vec::with_capacity(self.shaders.len());
+            let mut watched_files = Vec::new();
 
-            for Shader { gl_object_id, src } in &self.shaders {
-                gl::AttachShader(self.program, *gl_object_id);
-                shader_src_paths.push(src.clone());
+            for shader in &self.shaders {
+                gl::AttachShader(self.program, shader.gl_object_id);
+                shader_origins.push(shader.origin.clone());
+                shader_kinds.push(shader.kind);
+                if let ShaderOrigin::File(path) = &shader.origin {
+                    for file in std::iter::once(path).chain(&shader.includes) {
+                        watched_files.push((file.clone(), modified_time(file)));
+                    }
+                }
             }
 
             gl::LinkProgram(self.program);
@@ -59,18 +294,36 @@ impl Linker {
                 );
                 let reason = String::from_utf8_lossy(&log);
 
-                return Err(Error::ProgramLink(String::from(reason.trim())));
+                for Shader { gl_object_id, .. } in self.shaders {
+                    gl::DeleteShader(gl_object_id);
+                }
+                gl::DeleteProgram(self.program);
+                return Err(Error::ProgramLink(String::from(
+                    reason.trim_matches(|c: char| c == '\0' || c.is_whitespace()),
+                )));
             }
 
             for Shader { gl_object_id, .. } in self.shaders {
                 gl::DeleteShader(gl_object_id);
             }
-            let program = Program {
-                shader_src_paths,
+            let uniforms = reflection::query_uniforms(self.program);
+            let uniform_locations = uniforms
+                .iter()
+                .filter(|u| u.location != -1)
+                .map(|u| (u.name.clone(), u.location))
+                .collect();
+
+            Ok(Program {
+                shader_origins,
+                shader_kinds,
+                watched_files,
+                uniforms,
+                attributes: reflection::query_attributes(self.program),
+                uniform_locations: RefCell::new(uniform_locations),
+                last_uploader: Cell::new(None),
+                block_bindings: Vec::new(),
                 gl_object_id: self.program,
-            };
-            let obj_dec = ctx.register_object(GLObject::Program(program));
-            Ok(obj_dec)
+            })
         }
     }
 }
@@ -87,9 +340,9 @@ impl Drop for Program {
 impl Debug for Program {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let shader_srcs = self
-            .shader_src_paths
+            .shader_origins
             .iter()
-            .map(|p| p.to_string_lossy())
+            .map(ToString::to_string)
             .collect::<Vec<_>>()
             .join(",");
         write!(
@@ -99,3 +352,7 @@ impl Debug for Program {
         )
     }
 }
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
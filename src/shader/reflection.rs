@@ -0,0 +1,265 @@
+use crate::{internal_utils::try_into, uniform::UniformType};
+use gl::types::{GLchar, GLenum, GLint, GLsizei, GLuint};
+
+/// An active attribute or uniform of a linked [Program](super::Program) as reported by
+/// `glGetActiveAttrib`/`glGetActiveUniform`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveVariable {
+    /// Arrays are named without the `[0]` suffix OpenGL reports them with.
+    pub name: String,
+    pub gl_type: GLenum,
+    /// Number of elements for arrays, 1 otherwise.
+    pub size: GLint,
+    /// `-1` for uniforms that live in a uniform block.
+    pub location: GLint,
+}
+
+impl ActiveVariable {
+    pub fn type_name(&self) -> &'static str {
+        gl_type_name(self.gl_type)
+    }
+
+    /// Number of components of an attribute of this type, or `None` for types that can't be
+    /// sourced from float vertex data.
+    pub(crate) fn float_components(&self) -> Option<GLint> {
+        match self.gl_type {
+            gl::FLOAT => Some(1),
+            gl::FLOAT_VEC2 => Some(2),
+            gl::FLOAT_VEC3 => Some(3),
+            gl::FLOAT_VEC4 => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Number of components of an attribute of this type, or `None` for types that can't be
+    /// sourced from integer vertex data without conversion.
+    pub(crate) fn integer_components(&self) -> Option<GLint> {
+        match self.gl_type {
+            gl::INT | gl::UNSIGNED_INT => Some(1),
+            gl::INT_VEC2 | gl::UNSIGNED_INT_VEC2 => Some(2),
+            gl::INT_VEC3 | gl::UNSIGNED_INT_VEC3 => Some(3),
+            gl::INT_VEC4 | gl::UNSIGNED_INT_VEC4 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+impl UniformType {
+    /// Whether a uniform of this type can be used to set a GLSL variable of type `gl_type`.
+    /// Integers are accepted for booleans and samplers as well. Arrays are matched by their
+    /// element type.
+    pub fn matches_gl_type(&self, gl_type: GLenum) -> bool {
+        match self {
+            UniformType::D(_) | UniformType::DArray(_) => gl_type == gl::DOUBLE,
+            UniformType::F(_) | UniformType::FArray(_) => gl_type == gl::FLOAT,
+            UniformType::I(_) | UniformType::IArray(_) => {
+                matches!(gl_type, gl::INT | gl::BOOL) || is_opaque_type(gl_type)
+            }
+            UniformType::D2(_) | UniformType::D2Array(_) => gl_type == gl::DOUBLE_VEC2,
+            UniformType::F2(_) | UniformType::F2Array(_) => gl_type == gl::FLOAT_VEC2,
+            UniformType::I2(_) | UniformType::I2Array(_) => {
+                matches!(gl_type, gl::INT_VEC2 | gl::BOOL_VEC2)
+            }
+            UniformType::D3(_) | UniformType::D3Array(_) => gl_type == gl::DOUBLE_VEC3,
+            UniformType::F3(_) | UniformType::F3Array(_) => gl_type == gl::FLOAT_VEC3,
+            UniformType::I3(_) | UniformType::I3Array(_) => {
+                matches!(gl_type, gl::INT_VEC3 | gl::BOOL_VEC3)
+            }
+            UniformType::D4(_) | UniformType::D4Array(_) => gl_type == gl::DOUBLE_VEC4,
+            UniformType::F4(_) | UniformType::F4Array(_) => gl_type == gl::FLOAT_VEC4,
+            UniformType::I4(_) | UniformType::I4Array(_) => {
+                matches!(gl_type, gl::INT_VEC4 | gl::BOOL_VEC4)
+            }
+            UniformType::DMat2(_) | UniformType::DMat2Array(_) => gl_type == gl::DOUBLE_MAT2,
+            UniformType::FMat2(_) | UniformType::FMat2Array(_) => gl_type == gl::FLOAT_MAT2,
+            UniformType::DMat3(_) | UniformType::DMat3Array(_) => gl_type == gl::DOUBLE_MAT3,
+            UniformType::FMat3(_) | UniformType::FMat3Array(_) => gl_type == gl::FLOAT_MAT3,
+            UniformType::DMat4(_) | UniformType::DMat4Array(_) => gl_type == gl::DOUBLE_MAT4,
+            UniformType::FMat4(_) | UniformType::FMat4Array(_) => gl_type == gl::FLOAT_MAT4,
+        }
+    }
+
+    /// Name of the GLSL type this uniform type is primarily meant for.
+    pub fn glsl_name(&self) -> &'static str {
+        match self {
+            UniformType::D(_) => "double",
+            UniformType::F(_) => "float",
+            UniformType::I(_) => "int",
+            UniformType::D2(_) => "dvec2",
+            UniformType::F2(_) => "vec2",
+            UniformType::I2(_) => "ivec2",
+            UniformType::D3(_) => "dvec3",
+            UniformType::F3(_) => "vec3",
+            UniformType::I3(_) => "ivec3",
+            UniformType::D4(_) => "dvec4",
+            UniformType::F4(_) => "vec4",
+            UniformType::I4(_) => "ivec4",
+            UniformType::DMat2(_) => "dmat2",
+            UniformType::FMat2(_) => "mat2",
+            UniformType::DMat3(_) => "dmat3",
+            UniformType::FMat3(_) => "mat3",
+            UniformType::DMat4(_) => "dmat4",
+            UniformType::FMat4(_) => "mat4",
+            UniformType::DArray(_) => "double[]",
+            UniformType::FArray(_) => "float[]",
+            UniformType::IArray(_) => "int[]",
+            UniformType::D2Array(_) => "dvec2[]",
+            UniformType::F2Array(_) => "vec2[]",
+            UniformType::I2Array(_) => "ivec2[]",
+            UniformType::D3Array(_) => "dvec3[]",
+            UniformType::F3Array(_) => "vec3[]",
+            UniformType::I3Array(_) => "ivec3[]",
+            UniformType::D4Array(_) => "dvec4[]",
+            UniformType::F4Array(_) => "vec4[]",
+            UniformType::I4Array(_) => "ivec4[]",
+            UniformType::DMat2Array(_) => "dmat2[]",
+            UniformType::FMat2Array(_) => "mat2[]",
+            UniformType::DMat3Array(_) => "dmat3[]",
+            UniformType::FMat3Array(_) => "mat3[]",
+            UniformType::DMat4Array(_) => "dmat4[]",
+            UniformType::FMat4Array(_) => "mat4[]",
+        }
+    }
+}
+
+pub(crate) fn query_attributes(program: GLuint) -> Vec<ActiveVariable> {
+    unsafe {
+        query_active(
+            program,
+            gl::ACTIVE_ATTRIBUTES,
+            gl::ACTIVE_ATTRIBUTE_MAX_LENGTH,
+            |program, idx, buf_size, len, size, gl_type, name| {
+                gl::GetActiveAttrib(program, idx, buf_size, len, size, gl_type, name);
+                gl::GetAttribLocation(program, name)
+            },
+        )
+    }
+}
+
+pub(crate) fn query_uniforms(program: GLuint) -> Vec<ActiveVariable> {
+    unsafe {
+        query_active(
+            program,
+            gl::ACTIVE_UNIFORMS,
+            gl::ACTIVE_UNIFORM_MAX_LENGTH,
+            |program, idx, buf_size, len, size, gl_type, name| {
+                gl::GetActiveUniform(program, idx, buf_size, len, size, gl_type, name);
+                gl::GetUniformLocation(program, name)
+            },
+        )
+    }
+}
+
+type QueryFn =
+    unsafe fn(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLint, *mut GLenum, *mut GLchar) -> GLint;
+
+unsafe fn query_active(
+    program: GLuint,
+    count_param: GLenum,
+    max_len_param: GLenum,
+    query: QueryFn,
+) -> Vec<ActiveVariable> {
+    let mut count = 0;
+    let mut max_len = 0;
+    gl::GetProgramiv(program, count_param, &mut count);
+    gl::GetProgramiv(program, max_len_param, &mut max_len);
+
+    let mut name_buf = vec![0_u8; try_into!(max_len.max(1))];
+    let mut variables = Vec::with_capacity(try_into!(count));
+
+    for idx in 0..try_into!(count) {
+        let (mut len, mut size, mut gl_type) = (0, 0, 0);
+        let location = query(
+            program,
+            idx,
+            max_len,
+            &mut len,
+            &mut size,
+            &mut gl_type,
+            name_buf.as_mut_ptr() as *mut GLchar,
+        );
+        let name = String::from_utf8_lossy(&name_buf[..try_into!(len)]);
+        variables.push(ActiveVariable {
+            size,
+            gl_type,
+            location,
+            name: name.strip_suffix("[0]").unwrap_or(&name).to_string(),
+        });
+    }
+    variables
+}
+
+fn is_opaque_type(gl_type: GLenum) -> bool {
+    matches!(
+        gl_type,
+        gl::SAMPLER_1D
+            | gl::SAMPLER_2D
+            | gl::SAMPLER_3D
+            | gl::SAMPLER_CUBE
+            | gl::SAMPLER_1D_SHADOW
+            | gl::SAMPLER_2D_SHADOW
+            | gl::SAMPLER_1D_ARRAY
+            | gl::SAMPLER_2D_ARRAY
+            | gl::SAMPLER_2D_ARRAY_SHADOW
+            | gl::SAMPLER_CUBE_SHADOW
+            | gl::SAMPLER_2D_MULTISAMPLE
+            | gl::SAMPLER_2D_RECT
+            | gl::SAMPLER_BUFFER
+            | gl::INT_SAMPLER_2D
+            | gl::INT_SAMPLER_3D
+            | gl::INT_SAMPLER_CUBE
+            | gl::INT_SAMPLER_2D_ARRAY
+            | gl::UNSIGNED_INT_SAMPLER_2D
+            | gl::UNSIGNED_INT_SAMPLER_3D
+            | gl::UNSIGNED_INT_SAMPLER_CUBE
+            | gl::UNSIGNED_INT_SAMPLER_2D_ARRAY
+            | gl::IMAGE_2D
+            | gl::IMAGE_3D
+            | gl::IMAGE_CUBE
+            | gl::IMAGE_2D_ARRAY
+    )
+}
+
+pub fn gl_type_name(gl_type: GLenum) -> &'static str {
+    match gl_type {
+        gl::FLOAT => "float",
+        gl::FLOAT_VEC2 => "vec2",
+        gl::FLOAT_VEC3 => "vec3",
+        gl::FLOAT_VEC4 => "vec4",
+        gl::DOUBLE => "double",
+        gl::DOUBLE_VEC2 => "dvec2",
+        gl::DOUBLE_VEC3 => "dvec3",
+        gl::DOUBLE_VEC4 => "dvec4",
+        gl::INT => "int",
+        gl::INT_VEC2 => "ivec2",
+        gl::INT_VEC3 => "ivec3",
+        gl::INT_VEC4 => "ivec4",
+        gl::UNSIGNED_INT => "uint",
+        gl::UNSIGNED_INT_VEC2 => "uvec2",
+        gl::UNSIGNED_INT_VEC3 => "uvec3",
+        gl::UNSIGNED_INT_VEC4 => "uvec4",
+        gl::BOOL => "bool",
+        gl::BOOL_VEC2 => "bvec2",
+        gl::BOOL_VEC3 => "bvec3",
+        gl::BOOL_VEC4 => "bvec4",
+        gl::FLOAT_MAT2 => "mat2",
+        gl::FLOAT_MAT3 => "mat3",
+        gl::FLOAT_MAT4 => "mat4",
+        gl::FLOAT_MAT2x3 => "mat2x3",
+        gl::FLOAT_MAT2x4 => "mat2x4",
+        gl::FLOAT_MAT3x2 => "mat3x2",
+        gl::FLOAT_MAT3x4 => "mat3x4",
+        gl::FLOAT_MAT4x2 => "mat4x2",
+        gl::FLOAT_MAT4x3 => "mat4x3",
+        gl::DOUBLE_MAT2 => "dmat2",
+        gl::DOUBLE_MAT3 => "dmat3",
+        gl::DOUBLE_MAT4 => "dmat4",
+        gl::SAMPLER_2D => "sampler2D",
+        gl::SAMPLER_3D => "sampler3D",
+        gl::SAMPLER_CUBE => "samplerCube",
+        gl::SAMPLER_2D_SHADOW => "sampler2DShadow",
+        gl::SAMPLER_2D_ARRAY => "sampler2DArray",
+        _ if is_opaque_type(gl_type) => "opaque type",
+        _ => "unknown type",
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    context::{ClearMask, GLContext},
+    error::{misc_error, Error, Result},
+    framebuffer::{DepthStencilFormat, FramebufferBuilder},
+    internal_utils::try_into,
+    object::GLObject,
+    texture::TextureBuilder,
+};
+use image::{Rgba, RgbaImage};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// When set to anything other than `0` or an empty string, snapshot tests overwrite their
+/// reference images with whatever was rendered instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "GLOAM_UPDATE_SNAPSHOTS";
+
+/// Golden-image test for a rendered scene. The scene is rendered into an off-screen framebuffer
+/// of a fixed size and compared against a reference PNG at `<reference_dir>/<name>.png`. If too
+/// many pixels differ, the rendered image and a diff image highlighting the differing pixels in
+/// red are written next to the reference as `<name>.actual.png` and `<name>.diff.png`.
+///
+/// Meant to be used with a headless context running on a software rasterizer so that output is
+/// deterministic across machines.
+#[derive(Debug)]
+pub struct SnapshotTest {
+    name: String,
+    width: u32,
+    height: u32,
+    tolerance: u8,
+    max_differing_pixels: usize,
+    reference_dir: PathBuf,
+    clear_color: [f32; 4],
+}
+
+/// Result of comparing two images pixel by pixel.
+#[derive(Debug)]
+pub struct ImageDiff {
+    pub differing_pixels: usize,
+    /// Differing pixels in red over a faded copy of the actual image.
+    pub image: RgbaImage,
+}
+
+impl SnapshotTest {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            width: 256,
+            height: 256,
+            tolerance: 2,
+            max_differing_pixels: 0,
+            reference_dir: PathBuf::from("tests").join("snapshots"),
+            clear_color: [0.0, 0.0, 0.0, 1.0],
+        }
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Maximum difference allowed in any single channel before a pixel counts as differing.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Maximum number of differing pixels before the snapshot is considered a mismatch.
+    pub fn max_differing_pixels(mut self, max: usize) -> Self {
+        self.max_differing_pixels = max;
+        self
+    }
+
+    pub fn reference_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.reference_dir = dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// RGBA
+    pub fn clear_color(mut self, r: f32, g: f32, b: f32, a: f32) -> Self {
+        self.clear_color = [r, g, b, a];
+        self
+    }
+
+    pub fn reference_path(&self) -> PathBuf {
+        self.reference_dir.join(format!("{}.png", self.name))
+    }
+
+    /// Renders `render` off-screen and compares the result against the reference image. The
+    /// framebuffer is bound and its color, depth and stencil buffers are cleared before `render`
+    /// is called, so `render` should not bind framebuffers of its own. The viewport is restored
+    /// afterwards.
+    pub fn try_assert<F>(&self, ctx: &mut GLContext, render: F) -> Result<()>
+    where
+        F: FnOnce(&mut GLContext) -> Result<()>,
+    {
+        let actual = self.try_render(ctx, render)?;
+        let reference_path = self.reference_path();
+
+        if update_requested() {
+            fs::create_dir_all(&self.reference_dir).map_err(Error::boxed)?;
+            actual
+                .save(&reference_path)
+                .map_err(|e| misc_error!("failed to save reference image: {e}"))?;
+            log::info!("updated snapshot reference: {}", reference_path.display());
+            return Ok(());
+        }
+
+        if !reference_path.exists() {
+            return Err(Error::SnapshotMissing(reference_path));
+        }
+        let reference = image::open(&reference_path)
+            .map_err(|e| misc_error!("failed to load reference image: {e}"))?
+            .to_rgba8();
+
+        let diff = compare_images(&reference, &actual, self.tolerance);
+        if diff.differing_pixels <= self.max_differing_pixels {
+            return Ok(());
+        }
+
+        let actual_path = self.reference_dir.join(format!("{}.actual.png", self.name));
+        let diff_path = self.reference_dir.join(format!("{}.diff.png", self.name));
+        actual
+            .save(&actual_path)
+            .map_err(|e| misc_error!("failed to save rendered image: {e}"))?;
+        diff.image
+            .save(&diff_path)
+            .map_err(|e| misc_error!("failed to save diff image: {e}"))?;
+
+        Err(Error::SnapshotMismatch {
+            name: self.name.clone(),
+            differing_pixels: diff.differing_pixels,
+            max_differing_pixels: self.max_differing_pixels,
+            diff_path,
+        })
+    }
+
+    fn try_render<F>(&self, ctx: &mut GLContext, render: F) -> Result<RgbaImage>
+    where
+        F: FnOnce(&mut GLContext) -> Result<()>,
+    {
+        let framebuffer = FramebufferBuilder::new(self.width, self.height)
+            .color_attachment(TextureBuilder::new_2d_rgba8_empty(self.width, self.height))?
+            .depth_stencil_attachment(DepthStencilFormat::Depth24Stencil8)
+            .build(ctx)?;
+
+        // Only unbind when our own framebuffer was bound; if the caller has another one bound,
+        // the bind fails and their binding is left untouched.
+        let rendered = ctx.try_bind_framebuffer(framebuffer).and_then(|_| {
+            let mut previous_viewport = [0; 4];
+            unsafe { gl::GetIntegerv(gl::VIEWPORT, previous_viewport.as_mut_ptr()) };
+
+            let rendered = self.render_bound(ctx, render);
+
+            ctx.unbind_current_framebuffer();
+            let [x, y, width, height] = previous_viewport;
+            ctx.viewport(x, y, width, height);
+            rendered
+        });
+
+        if let Some(GLObject::Framebuffer(fb)) = ctx.remove_object(framebuffer) {
+            for texture in fb.color_attachments() {
+                ctx.remove_object(*texture);
+            }
+        }
+        rendered
+    }
+
+    /// Clears the currently bound framebuffer, runs `render` on it and reads the result back.
+    fn render_bound<F>(&self, ctx: &mut GLContext, render: F) -> Result<RgbaImage>
+    where
+        F: FnOnce(&mut GLContext) -> Result<()>,
+    {
+        let (width, height) = (try_into!(self.width), try_into!(self.height));
+        let [r, g, b, a] = self.clear_color;
+        ctx.viewport(0, 0, width, height);
+        ctx.clear(&[
+            ClearMask::Color(r, g, b, a),
+            ClearMask::DepthBuffer,
+            ClearMask::Stencil(0),
+        ]);
+        render(ctx)?;
+        ctx.try_read_pixels(0, 0, width, height)
+    }
+}
+
+/// Compares two images pixel by pixel; a pixel differs when any of its channels differs by more
+/// than `tolerance`. Images of different dimensions differ in every pixel.
+pub fn compare_images(reference: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> ImageDiff {
+    let mut image = RgbaImage::new(actual.width(), actual.height());
+
+    if reference.dimensions() != actual.dimensions() {
+        for pixel in image.pixels_mut() {
+            *pixel = Rgba([255, 0, 0, 255]);
+        }
+        return ImageDiff {
+            image,
+            differing_pixels: try_into!(actual.width() * actual.height()),
+        };
+    }
+
+    let mut differing_pixels = 0;
+    for ((expected, found), out) in reference
+        .pixels()
+        .zip(actual.pixels())
+        .zip(image.pixels_mut())
+    {
+        let differs = expected
+            .0
+            .iter()
+            .zip(found.0.iter())
+            .any(|(a, b)| a.abs_diff(*b) > tolerance);
+
+        if differs {
+            differing_pixels += 1;
+            *out = Rgba([255, 0, 0, 255]);
+        } else {
+            let [r, g, b, _] = found.0;
+            let luma = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+            *out = Rgba([luma / 3, luma / 3, luma / 3, 255]);
+        }
+    }
+    ImageDiff {
+        image,
+        differing_pixels,
+    }
+}
+
+fn update_requested() -> bool {
+    env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
@@ -0,0 +1,104 @@
+use gl::types::GLenum;
+
+/// Blend equations and factors for the color and alpha channels, i.e. `glBlendEquationSeparate`
+/// and `glBlendFuncSeparate`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BlendState {
+    pub color_equation: BlendEquation,
+    pub alpha_equation: BlendEquation,
+    pub src_color: BlendFactor,
+    pub dst_color: BlendFactor,
+    pub src_alpha: BlendFactor,
+    pub dst_alpha: BlendFactor,
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendEquation {
+    #[default]
+    Add,
+    Subtract,
+    ReverseSubtract,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendFactor {
+    Zero,
+    One,
+    SrcColor,
+    OneMinusSrcColor,
+    DstColor,
+    OneMinusDstColor,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    DstAlpha,
+    OneMinusDstAlpha,
+    ConstantColor,
+    OneMinusConstantColor,
+    ConstantAlpha,
+    OneMinusConstantAlpha,
+    SrcAlphaSaturate,
+}
+
+impl BlendState {
+    /// Same equation and factors for both color and alpha.
+    pub fn new(equation: BlendEquation, src: BlendFactor, dst: BlendFactor) -> Self {
+        Self {
+            color_equation: equation,
+            alpha_equation: equation,
+            src_color: src,
+            dst_color: dst,
+            src_alpha: src,
+            dst_alpha: dst,
+        }
+    }
+
+    /// Conventional transparency: `src * src_alpha + dst * (1 - src_alpha)`.
+    pub fn alpha() -> Self {
+        Self::new(
+            BlendEquation::Add,
+            BlendFactor::SrcAlpha,
+            BlendFactor::OneMinusSrcAlpha,
+        )
+    }
+
+    /// `src + dst`
+    pub fn additive() -> Self {
+        Self::new(BlendEquation::Add, BlendFactor::One, BlendFactor::One)
+    }
+}
+
+impl From<BlendEquation> for GLenum {
+    fn from(value: BlendEquation) -> Self {
+        match value {
+            BlendEquation::Add => gl::FUNC_ADD,
+            BlendEquation::Subtract => gl::FUNC_SUBTRACT,
+            BlendEquation::ReverseSubtract => gl::FUNC_REVERSE_SUBTRACT,
+            BlendEquation::Min => gl::MIN,
+            BlendEquation::Max => gl::MAX,
+        }
+    }
+}
+
+impl From<BlendFactor> for GLenum {
+    fn from(value: BlendFactor) -> Self {
+        match value {
+            BlendFactor::Zero => gl::ZERO,
+            BlendFactor::One => gl::ONE,
+            BlendFactor::SrcColor => gl::SRC_COLOR,
+            BlendFactor::OneMinusSrcColor => gl::ONE_MINUS_SRC_COLOR,
+            BlendFactor::DstColor => gl::DST_COLOR,
+            BlendFactor::OneMinusDstColor => gl::ONE_MINUS_DST_COLOR,
+            BlendFactor::SrcAlpha => gl::SRC_ALPHA,
+            BlendFactor::OneMinusSrcAlpha => gl::ONE_MINUS_SRC_ALPHA,
+            BlendFactor::DstAlpha => gl::DST_ALPHA,
+            BlendFactor::OneMinusDstAlpha => gl::ONE_MINUS_DST_ALPHA,
+            BlendFactor::ConstantColor => gl::CONSTANT_COLOR,
+            BlendFactor::OneMinusConstantColor => gl::ONE_MINUS_CONSTANT_COLOR,
+            BlendFactor::ConstantAlpha => gl::CONSTANT_ALPHA,
+            BlendFactor::OneMinusConstantAlpha => gl::ONE_MINUS_CONSTANT_ALPHA,
+            BlendFactor::SrcAlphaSaturate => gl::SRC_ALPHA_SATURATE,
+        }
+    }
+}
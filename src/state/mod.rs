@@ -0,0 +1,168 @@
+use crate::error::{misc_error, Error};
+use gl::types::{GLdouble, GLenum, GLfloat, GLint, GLsizei, GLuint};
+
+pub mod blend;
+pub use blend::{BlendEquation, BlendFactor, BlendState};
+
+pub mod stencil;
+pub use stencil::{StencilOp, StencilState};
+
+/// Fixed-function state used when drawing. [Default] matches OpenGL's initial state. Apply it with
+/// [GLContext::apply_render_state](crate::context::GLContext::apply_render_state).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RenderState {
+    /// `None` disables blending.
+    pub blend: Option<BlendState>,
+    /// `None` disables face culling.
+    pub cull_face: Option<CullFace>,
+    pub front_face: FrontFace,
+    pub polygon_mode: PolygonMode,
+    /// `None` disables the depth test.
+    pub depth_test: Option<CompareFunc>,
+    pub depth_write: bool,
+    /// Near and far values of `glDepthRange`.
+    pub depth_range: (GLdouble, GLdouble),
+    /// `None` disables polygon offset.
+    pub polygon_offset: Option<PolygonOffset>,
+    /// `None` disables the stencil test.
+    pub stencil_test: Option<StencilState>,
+    /// Bits of the stencil buffer that can be written, including by clears.
+    pub stencil_write_mask: GLuint,
+    /// `None` disables the scissor test. Clears are limited to the scissor box as well.
+    pub scissor: Option<Scissor>,
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CullFace {
+    Front,
+    #[default]
+    Back,
+    FrontAndBack,
+}
+
+/// Winding order of front-facing polygons.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrontFace {
+    Clockwise,
+    #[default]
+    CounterClockwise,
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PolygonMode {
+    Point,
+    Line,
+    #[default]
+    Fill,
+}
+
+/// Comparison used by the depth and stencil tests.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CompareFunc {
+    Never,
+    #[default]
+    Less,
+    Equal,
+    LessEqual,
+    Greater,
+    NotEqual,
+    GreaterEqual,
+    Always,
+}
+
+/// Rectangle in window coordinates outside of which fragments are discarded. `x` and `y` are the
+/// lower-left corner.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Scissor {
+    pub x: GLint,
+    pub y: GLint,
+    pub width: GLsizei,
+    pub height: GLsizei,
+}
+
+/// Depth offset applied to polygons i.e. `glPolygonOffset`. Applies to all polygon modes.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct PolygonOffset {
+    pub factor: GLfloat,
+    pub units: GLfloat,
+}
+
+impl Default for RenderState {
+    fn default() -> Self {
+        Self {
+            blend: None,
+            cull_face: None,
+            front_face: FrontFace::default(),
+            polygon_mode: PolygonMode::default(),
+            depth_test: None,
+            depth_write: true,
+            depth_range: (0.0, 1.0),
+            polygon_offset: None,
+            stencil_test: None,
+            stencil_write_mask: GLuint::MAX,
+            scissor: None,
+        }
+    }
+}
+
+impl From<CullFace> for GLenum {
+    fn from(value: CullFace) -> Self {
+        match value {
+            CullFace::Front => gl::FRONT,
+            CullFace::Back => gl::BACK,
+            CullFace::FrontAndBack => gl::FRONT_AND_BACK,
+        }
+    }
+}
+
+impl From<FrontFace> for GLenum {
+    fn from(value: FrontFace) -> Self {
+        match value {
+            FrontFace::Clockwise => gl::CW,
+            FrontFace::CounterClockwise => gl::CCW,
+        }
+    }
+}
+
+impl From<PolygonMode> for GLenum {
+    fn from(value: PolygonMode) -> Self {
+        match value {
+            PolygonMode::Point => gl::POINT,
+            PolygonMode::Line => gl::LINE,
+            PolygonMode::Fill => gl::FILL,
+        }
+    }
+}
+
+impl From<CompareFunc> for GLenum {
+    fn from(value: CompareFunc) -> Self {
+        match value {
+            CompareFunc::Never => gl::NEVER,
+            CompareFunc::Less => gl::LESS,
+            CompareFunc::Equal => gl::EQUAL,
+            CompareFunc::LessEqual => gl::LEQUAL,
+            CompareFunc::Greater => gl::GREATER,
+            CompareFunc::NotEqual => gl::NOTEQUAL,
+            CompareFunc::GreaterEqual => gl::GEQUAL,
+            CompareFunc::Always => gl::ALWAYS,
+        }
+    }
+}
+
+impl TryFrom<GLenum> for CompareFunc {
+    type Error = Error;
+
+    fn try_from(value: GLenum) -> Result<Self, Self::Error> {
+        match value {
+            gl::NEVER => Ok(CompareFunc::Never),
+            gl::LESS => Ok(CompareFunc::Less),
+            gl::EQUAL => Ok(CompareFunc::Equal),
+            gl::LEQUAL => Ok(CompareFunc::LessEqual),
+            gl::GREATER => Ok(CompareFunc::Greater),
+            gl::NOTEQUAL => Ok(CompareFunc::NotEqual),
+            gl::GEQUAL => Ok(CompareFunc::GreaterEqual),
+            gl::ALWAYS => Ok(CompareFunc::Always),
+            _ => Err(misc_error!("{value:#x} is not a valid comparison function")),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use super::CompareFunc;
+use gl::types::{GLenum, GLint, GLuint};
+
+/// Stencil test and the operations applied to the stencil buffer depending on its outcome, i.e.
+/// `glStencilFunc` and `glStencilOp`. Applies to both front and back faces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StencilState {
+    pub func: CompareFunc,
+    /// Value compared against the stencil buffer and written by [StencilOp::Replace].
+    pub reference: GLint,
+    /// ANDed with both the reference value and the stored value before comparing.
+    pub read_mask: GLuint,
+    /// Stencil test fails.
+    pub fail: StencilOp,
+    /// Stencil test passes but the depth test fails.
+    pub depth_fail: StencilOp,
+    /// Both stencil and depth tests pass.
+    pub pass: StencilOp,
+}
+
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StencilOp {
+    #[default]
+    Keep,
+    Zero,
+    Replace,
+    Increment,
+    IncrementWrap,
+    Decrement,
+    DecrementWrap,
+    Invert,
+}
+
+impl StencilState {
+    /// Passes where the stencil buffer satisfies `func` against `reference` and leaves it as is.
+    pub fn test(func: CompareFunc, reference: GLint) -> Self {
+        Self {
+            func,
+            reference,
+            read_mask: GLuint::MAX,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+        }
+    }
+
+    /// Always passes and writes `reference` wherever a fragment passes the depth test.
+    pub fn write(reference: GLint) -> Self {
+        Self {
+            pass: StencilOp::Replace,
+            ..Self::test(CompareFunc::Always, reference)
+        }
+    }
+}
+
+impl Default for StencilState {
+    fn default() -> Self {
+        Self::test(CompareFunc::Always, 0)
+    }
+}
+
+impl From<StencilOp> for GLenum {
+    fn from(value: StencilOp) -> Self {
+        match value {
+            StencilOp::Keep => gl::KEEP,
+            StencilOp::Zero => gl::ZERO,
+            StencilOp::Replace => gl::REPLACE,
+            StencilOp::Increment => gl::INCR,
+            StencilOp::IncrementWrap => gl::INCR_WRAP,
+            StencilOp::Decrement => gl::DECR,
+            StencilOp::DecrementWrap => gl::DECR_WRAP,
+            StencilOp::Invert => gl::INVERT,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     context::GLContext,
     error::{misc_error, Error, Result},
     internal_utils::try_into,
-    object::{GLObject, GLObjectDescriptor},
+    object::Handle,
 };
 use gl::types::{GLenum, GLuint};
 use image::{DynamicImage, GenericImageView};
@@ -11,6 +11,7 @@ use std::{
     fmt::Debug,
     ops::Drop,
     path::{Path, PathBuf},
+    ptr,
 };
 
 pub mod filter;
@@ -95,6 +96,30 @@ impl TextureBuilder {
         })
     }
 
+    /// An RGBA8 2D texture without any image data; its contents are undefined until rendered to
+    /// e.g. as a color attachment of a [Framebuffer].
+    ///
+    /// [Framebuffer]: crate::framebuffer::Framebuffer
+    pub fn new_2d_rgba8_empty(width: u32, height: u32) -> Self {
+        TextureBuilder {
+            width,
+            height,
+            data: Vec::new(),
+            kind: TextureType::Texture2D,
+            s_wrap: None,
+            t_wrap: None,
+            r_wrap: None,
+            min_filter: None,
+            mag_filter: None,
+            format: gl::RGBA,
+            src: PathBuf::new(),
+        }
+    }
+
+    pub(crate) fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
     pub fn s_wrap(mut self, param: TextureWrapParam) -> Self {
         self.s_wrap = Some(param);
         self
@@ -120,11 +145,11 @@ impl TextureBuilder {
         self
     }
 
-    pub fn build(self, ctx: &mut GLContext) -> Result<GLObjectDescriptor> {
+    pub fn build(self, ctx: &mut GLContext) -> Result<Handle<Texture>> {
         unsafe { self.build_impl(ctx) }
     }
 
-    unsafe fn build_impl(self, ctx: &mut GLContext) -> Result<GLObjectDescriptor> {
+    unsafe fn build_impl(self, ctx: &mut GLContext) -> Result<Handle<Texture>> {
         let TextureBuilder {
             data,
             src,
@@ -186,7 +211,11 @@ impl TextureBuilder {
                 0,
                 try_into!(format),
                 gl::UNSIGNED_BYTE,
-                data.as_ptr() as *const c_void,
+                if data.is_empty() {
+                    ptr::null()
+                } else {
+                    data.as_ptr() as *const c_void
+                },
             ),
             TextureType::Texture3D => todo!(),
             TextureType::TextureCubeMap => todo!(),
@@ -202,8 +231,8 @@ impl TextureBuilder {
             src,
             gl_object_id: texture,
         };
-        let obj_desc = ctx.register_object(GLObject::Texture(texture));
-        Ok(obj_desc)
+        let handle = ctx.register(texture);
+        Ok(handle)
     }
 }
 
@@ -0,0 +1,131 @@
+use super::std140::{Std140, Std140Writer};
+use crate::{
+    context::GLContext,
+    error::{Error, Result},
+    internal_utils::try_into,
+    object::Handle,
+    vertex::Usage,
+};
+use gl::types::GLuint;
+use std::{ffi::c_void, fmt::Debug, ops::Drop};
+
+/// A buffer backing `uniform` blocks. Bind it to a binding point with
+/// [GLContext::try_bind_uniform_buffer] and point the blocks of each program at that binding
+/// with [GLContext::try_bind_uniform_block] so that every program shares the same data.
+#[derive(Debug)]
+pub struct UniformBuffer {
+    pub gl_object_id: GLuint,
+    pub size: usize,
+    pub usage: Usage,
+}
+
+#[derive(Debug)]
+pub struct UniformBufferBuilder {
+    usage: Usage,
+    size: usize,
+    data: Vec<u8>,
+}
+
+impl UniformBufferBuilder {
+    pub fn new(usage: Usage) -> Self {
+        Self {
+            usage,
+            size: 0,
+            data: Vec::new(),
+        }
+    }
+
+    /// Size of the buffer in bytes. Defaults to the size of the initial data; space not covered
+    /// by it is zeroed.
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Initial contents, usually packed with a [Std140Writer].
+    pub fn data(mut self, data: &[u8]) -> Self {
+        self.data = data.to_vec();
+        self
+    }
+
+    /// Initial contents packed following the std140 layout.
+    pub fn std140<T: Std140 + ?Sized>(self, value: &T) -> Self {
+        let mut writer = Std140Writer::new();
+        writer.write(value);
+        self.data(&writer.into_bytes())
+    }
+
+    pub fn build(self, ctx: &mut GLContext) -> Result<Handle<UniformBuffer>> {
+        unsafe { self.build_impl(ctx) }
+    }
+
+    unsafe fn build_impl(self, ctx: &mut GLContext) -> Result<Handle<UniformBuffer>> {
+        let UniformBufferBuilder {
+            usage,
+            size,
+            mut data,
+        } = self;
+
+        let size = size.max(data.len());
+        if size == 0 {
+            return Err(Error::InvalidUniformBuffer(
+                "can't create an empty uniform buffer".to_string(),
+            ));
+        }
+        data.resize(size, 0);
+
+        let mut ubo = 0;
+        gl::GenBuffers(1, &mut ubo);
+        gl::BindBuffer(gl::UNIFORM_BUFFER, ubo);
+        gl::BufferData(
+            gl::UNIFORM_BUFFER,
+            try_into!(size),
+            data.as_ptr() as *const c_void,
+            usage.into(),
+        );
+        gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+
+        let handle = ctx.register(UniformBuffer {
+            size,
+            usage,
+            gl_object_id: ubo,
+        });
+        Ok(handle)
+    }
+}
+
+impl UniformBuffer {
+    /// Overwrites the contents of the buffer starting at byte `offset`.
+    pub(crate) fn write(&self, offset: usize, data: &[u8]) -> Result<()> {
+        if offset
+            .checked_add(data.len())
+            .is_none_or(|end| end > self.size)
+        {
+            return Err(Error::InvalidUniformBuffer(format!(
+                "writing {} bytes at offset {offset} overflows buffer of {} bytes",
+                data.len(),
+                self.size
+            )));
+        }
+        unsafe {
+            gl::BindBuffer(gl::UNIFORM_BUFFER, self.gl_object_id);
+            gl::BufferSubData(
+                gl::UNIFORM_BUFFER,
+                try_into!(offset),
+                try_into!(data.len()),
+                data.as_ptr() as *const c_void,
+            );
+            gl::BindBuffer(gl::UNIFORM_BUFFER, 0);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for UniformBuffer {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.gl_object_id);
+        }
+        self.gl_object_id = 0;
+    }
+}
@@ -1,6 +1,23 @@
 use gl::types::{GLdouble, GLfloat, GLint};
 use nalgebra_glm as glm;
 
+pub mod buffer;
+pub use buffer::{UniformBuffer, UniformBufferBuilder};
+
+pub mod path;
+pub use path::UniformPath;
+
+pub mod set;
+pub use gloam_derive::Uniforms;
+pub use set::{AsUniformType, Uniforms};
+
+pub mod std140;
+pub use std140::{Std140, Std140Writer};
+
+/// A value for a uniform variable. Members of GLSL structs are set individually by name, e.g.
+/// `light.position` or `lights[3].position`; see [UniformPath]. Array variants set consecutive
+/// elements of an array starting at the one named, i.e. `lights` or `lights[0]` for the whole
+/// array.
 #[derive(Debug)]
 pub struct Uniform {
     pub name: String,
@@ -30,6 +47,24 @@ pub enum UniformType {
     FMat3(glm::TMat3<GLfloat>),
     DMat4(glm::TMat4<GLdouble>),
     FMat4(glm::TMat4<GLfloat>),
+    DArray(Vec<GLdouble>),
+    FArray(Vec<GLfloat>),
+    IArray(Vec<GLint>),
+    D2Array(Vec<glm::TVec2<GLdouble>>),
+    F2Array(Vec<glm::TVec2<GLfloat>>),
+    I2Array(Vec<glm::TVec2<GLint>>),
+    D3Array(Vec<glm::TVec3<GLdouble>>),
+    F3Array(Vec<glm::TVec3<GLfloat>>),
+    I3Array(Vec<glm::TVec3<GLint>>),
+    D4Array(Vec<glm::TVec4<GLdouble>>),
+    F4Array(Vec<glm::TVec4<GLfloat>>),
+    I4Array(Vec<glm::TVec4<GLint>>),
+    DMat2Array(Vec<glm::TMat2<GLdouble>>),
+    FMat2Array(Vec<glm::TMat2<GLfloat>>),
+    DMat3Array(Vec<glm::TMat3<GLdouble>>),
+    FMat3Array(Vec<glm::TMat3<GLfloat>>),
+    DMat4Array(Vec<glm::TMat4<GLdouble>>),
+    FMat4Array(Vec<glm::TMat4<GLfloat>>),
 }
 
 impl Uniform {
@@ -113,6 +148,90 @@ impl Uniform {
         Self::new(name, UniformType::FMat4(value), transpose)
     }
 
+    pub fn new_1d_array(name: &str, values: Vec<GLdouble>) -> Self {
+        Self::new(name, UniformType::DArray(values), false)
+    }
+
+    pub fn new_1f_array(name: &str, values: Vec<GLfloat>) -> Self {
+        Self::new(name, UniformType::FArray(values), false)
+    }
+
+    pub fn new_1i_array(name: &str, values: Vec<GLint>) -> Self {
+        Self::new(name, UniformType::IArray(values), false)
+    }
+
+    pub fn new_2d_array(name: &str, values: Vec<glm::TVec2<GLdouble>>) -> Self {
+        Self::new(name, UniformType::D2Array(values), false)
+    }
+
+    pub fn new_2f_array(name: &str, values: Vec<glm::TVec2<GLfloat>>) -> Self {
+        Self::new(name, UniformType::F2Array(values), false)
+    }
+
+    pub fn new_2i_array(name: &str, values: Vec<glm::TVec2<GLint>>) -> Self {
+        Self::new(name, UniformType::I2Array(values), false)
+    }
+
+    pub fn new_3d_array(name: &str, values: Vec<glm::TVec3<GLdouble>>) -> Self {
+        Self::new(name, UniformType::D3Array(values), false)
+    }
+
+    pub fn new_3f_array(name: &str, values: Vec<glm::TVec3<GLfloat>>) -> Self {
+        Self::new(name, UniformType::F3Array(values), false)
+    }
+
+    pub fn new_3i_array(name: &str, values: Vec<glm::TVec3<GLint>>) -> Self {
+        Self::new(name, UniformType::I3Array(values), false)
+    }
+
+    pub fn new_4d_array(name: &str, values: Vec<glm::TVec4<GLdouble>>) -> Self {
+        Self::new(name, UniformType::D4Array(values), false)
+    }
+
+    pub fn new_4f_array(name: &str, values: Vec<glm::TVec4<GLfloat>>) -> Self {
+        Self::new(name, UniformType::F4Array(values), false)
+    }
+
+    pub fn new_4i_array(name: &str, values: Vec<glm::TVec4<GLint>>) -> Self {
+        Self::new(name, UniformType::I4Array(values), false)
+    }
+
+    pub fn new_mat2dv_array(
+        name: &str,
+        values: Vec<glm::TMat2<GLdouble>>,
+        transpose: bool,
+    ) -> Self {
+        Self::new(name, UniformType::DMat2Array(values), transpose)
+    }
+
+    pub fn new_mat2fv_array(name: &str, values: Vec<glm::TMat2<GLfloat>>, transpose: bool) -> Self {
+        Self::new(name, UniformType::FMat2Array(values), transpose)
+    }
+
+    pub fn new_mat3dv_array(
+        name: &str,
+        values: Vec<glm::TMat3<GLdouble>>,
+        transpose: bool,
+    ) -> Self {
+        Self::new(name, UniformType::DMat3Array(values), transpose)
+    }
+
+    pub fn new_mat3fv_array(name: &str, values: Vec<glm::TMat3<GLfloat>>, transpose: bool) -> Self {
+        Self::new(name, UniformType::FMat3Array(values), transpose)
+    }
+
+    pub fn new_mat4dv_array(
+        name: &str,
+        values: Vec<glm::TMat4<GLdouble>>,
+        transpose: bool,
+    ) -> Self {
+        Self::new(name, UniformType::DMat4Array(values), transpose)
+    }
+
+    pub fn new_mat4fv_array(name: &str, values: Vec<glm::TMat4<GLfloat>>, transpose: bool) -> Self {
+        Self::new(name, UniformType::FMat4Array(values), transpose)
+    }
+
     pub fn default_1d(name: &str) -> Self {
         Self::new(name, UniformType::D(0.0), false)
     }
@@ -233,3 +352,61 @@ impl Uniform {
         )
     }
 }
+
+impl UniformType {
+    /// Number of array elements this value covers; 1 for non-array values.
+    pub fn count(&self) -> usize {
+        match self {
+            UniformType::DArray(v) => v.len(),
+            UniformType::FArray(v) => v.len(),
+            UniformType::IArray(v) => v.len(),
+            UniformType::D2Array(v) => v.len(),
+            UniformType::F2Array(v) => v.len(),
+            UniformType::I2Array(v) => v.len(),
+            UniformType::D3Array(v) => v.len(),
+            UniformType::F3Array(v) => v.len(),
+            UniformType::I3Array(v) => v.len(),
+            UniformType::D4Array(v) => v.len(),
+            UniformType::F4Array(v) => v.len(),
+            UniformType::I4Array(v) => v.len(),
+            UniformType::DMat2Array(v) => v.len(),
+            UniformType::FMat2Array(v) => v.len(),
+            UniformType::DMat3Array(v) => v.len(),
+            UniformType::FMat3Array(v) => v.len(),
+            UniformType::DMat4Array(v) => v.len(),
+            UniformType::FMat4Array(v) => v.len(),
+            _ => 1,
+        }
+    }
+
+    /// Replaces element `idx` of an array value with the non-array `value` of the matching type.
+    /// Returns false and leaves `self` untouched if the types don't match or `idx` is out of
+    /// range.
+    pub fn set_element(&mut self, idx: usize, value: UniformType) -> bool {
+        if idx >= self.count() {
+            return false;
+        }
+        match (self, value) {
+            (UniformType::DArray(values), UniformType::D(v)) => values[idx] = v,
+            (UniformType::FArray(values), UniformType::F(v)) => values[idx] = v,
+            (UniformType::IArray(values), UniformType::I(v)) => values[idx] = v,
+            (UniformType::D2Array(values), UniformType::D2(v)) => values[idx] = v,
+            (UniformType::F2Array(values), UniformType::F2(v)) => values[idx] = v,
+            (UniformType::I2Array(values), UniformType::I2(v)) => values[idx] = v,
+            (UniformType::D3Array(values), UniformType::D3(v)) => values[idx] = v,
+            (UniformType::F3Array(values), UniformType::F3(v)) => values[idx] = v,
+            (UniformType::I3Array(values), UniformType::I3(v)) => values[idx] = v,
+            (UniformType::D4Array(values), UniformType::D4(v)) => values[idx] = v,
+            (UniformType::F4Array(values), UniformType::F4(v)) => values[idx] = v,
+            (UniformType::I4Array(values), UniformType::I4(v)) => values[idx] = v,
+            (UniformType::DMat2Array(values), UniformType::DMat2(v)) => values[idx] = v,
+            (UniformType::FMat2Array(values), UniformType::FMat2(v)) => values[idx] = v,
+            (UniformType::DMat3Array(values), UniformType::DMat3(v)) => values[idx] = v,
+            (UniformType::FMat3Array(values), UniformType::FMat3(v)) => values[idx] = v,
+            (UniformType::DMat4Array(values), UniformType::DMat4(v)) => values[idx] = v,
+            (UniformType::FMat4Array(values), UniformType::FMat4(v)) => values[idx] = v,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    fmt::{self, Display},
+    ops::Deref,
+};
+
+/// Builds the name of a struct member or array element uniform e.g.
+/// `UniformPath::new("lights").index(3).field("position")` for `lights[3].position`. Derefs to
+/// `str` so it can be passed as the name of any [Uniform](super::Uniform) constructor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UniformPath(String);
+
+impl UniformPath {
+    pub fn new(name: &str) -> Self {
+        Self(name.to_string())
+    }
+
+    pub fn index(mut self, idx: usize) -> Self {
+        self.0.push_str(&format!("[{idx}]"));
+        self
+    }
+
+    pub fn field(mut self, name: &str) -> Self {
+        self.0.push('.');
+        self.0.push_str(name);
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for UniformPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for UniformPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<UniformPath> for String {
+    fn from(path: UniformPath) -> Self {
+        path.0
+    }
+}
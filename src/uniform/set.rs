@@ -0,0 +1,76 @@
+use super::{Uniform, UniformType};
+use crate::{error::Result, mesh::Mesh};
+use gl::types::{GLdouble, GLfloat, GLint};
+use nalgebra_glm as glm;
+
+/// A group of uniforms kept in a Rust struct, usually implemented with
+/// `#[derive(Uniforms)]`; see [gloam_derive::Uniforms].
+pub trait Uniforms {
+    /// One [Uniform] per field, e.g. to initialize a [Mesh] with.
+    fn uniforms(&self) -> Vec<Uniform>;
+
+    /// Updates every uniform of `mesh` CPU-side with the values of this struct. See
+    /// [Mesh::set_uniform_on_cpu].
+    fn update_mesh(&self, mesh: &mut Mesh) -> Result<()> {
+        for uniform in self.uniforms() {
+            mesh.set_uniform_on_cpu(uniform)?;
+        }
+        Ok(())
+    }
+}
+
+/// Rust types that can be the value of a [Uniform].
+pub trait AsUniformType {
+    fn as_uniform_type(&self) -> UniformType;
+}
+
+impl AsUniformType for bool {
+    fn as_uniform_type(&self) -> UniformType {
+        UniformType::I(GLint::from(*self))
+    }
+}
+
+macro_rules! impl_as_uniform_type {
+    ($($ty:ty => $variant:ident, $array_variant:ident;)*) => {
+        $(
+            impl AsUniformType for $ty {
+                fn as_uniform_type(&self) -> UniformType {
+                    UniformType::$variant(*self)
+                }
+            }
+
+            impl AsUniformType for Vec<$ty> {
+                fn as_uniform_type(&self) -> UniformType {
+                    UniformType::$array_variant(self.clone())
+                }
+            }
+
+            impl<const N: usize> AsUniformType for [$ty; N] {
+                fn as_uniform_type(&self) -> UniformType {
+                    UniformType::$array_variant(self.to_vec())
+                }
+            }
+        )*
+    };
+}
+
+impl_as_uniform_type! {
+    GLdouble => D, DArray;
+    GLfloat => F, FArray;
+    GLint => I, IArray;
+    glm::TVec2<GLdouble> => D2, D2Array;
+    glm::TVec2<GLfloat> => F2, F2Array;
+    glm::TVec2<GLint> => I2, I2Array;
+    glm::TVec3<GLdouble> => D3, D3Array;
+    glm::TVec3<GLfloat> => F3, F3Array;
+    glm::TVec3<GLint> => I3, I3Array;
+    glm::TVec4<GLdouble> => D4, D4Array;
+    glm::TVec4<GLfloat> => F4, F4Array;
+    glm::TVec4<GLint> => I4, I4Array;
+    glm::TMat2<GLdouble> => DMat2, DMat2Array;
+    glm::TMat2<GLfloat> => FMat2, FMat2Array;
+    glm::TMat3<GLdouble> => DMat3, DMat3Array;
+    glm::TMat3<GLfloat> => FMat3, FMat3Array;
+    glm::TMat4<GLdouble> => DMat4, DMat4Array;
+    glm::TMat4<GLfloat> => FMat4, FMat4Array;
+}
@@ -0,0 +1,383 @@
+use gl::types::{GLdouble, GLfloat, GLint, GLuint};
+use nalgebra_glm as glm;
+
+/// Types that can be written to a uniform block declared with `layout(std140)`. Structures
+/// implement this by writing their members in declaration order with [Std140Writer::write],
+/// setting [Std140::ALIGNMENT] to the [struct_alignment] of their members and
+/// [Std140::ROUND_UP_SIZE] to true.
+pub trait Std140 {
+    /// Base alignment in bytes.
+    const ALIGNMENT: usize;
+
+    /// Whether the space taken up is rounded up to a multiple of [Std140::ALIGNMENT], as it is
+    /// for arrays, matrices and structures.
+    const ROUND_UP_SIZE: bool = false;
+
+    /// Writes the value without any leading or trailing padding; use [Std140Writer::write].
+    fn write_std140(&self, writer: &mut Std140Writer);
+}
+
+/// Packs values into a byte buffer following the std140 layout rules, inserting the padding
+/// that OpenGL expects between them.
+#[derive(Debug, Default, Clone)]
+pub struct Std140Writer {
+    buf: Vec<u8>,
+}
+
+/// Base alignment of a structure with members of the given alignments.
+pub const fn struct_alignment(members: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < members.len() {
+        if members[i] > max {
+            max = members[i];
+        }
+        i += 1;
+    }
+    round_up(max, 16)
+}
+
+impl Std140Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `value` at the next offset that satisfies its alignment.
+    pub fn write<T: Std140 + ?Sized>(&mut self, value: &T) -> &mut Self {
+        self.align(T::ALIGNMENT);
+        value.write_std140(self);
+        if T::ROUND_UP_SIZE {
+            self.align(T::ALIGNMENT);
+        }
+        self
+    }
+
+    /// Pads with zeroes up to the next multiple of `alignment`.
+    pub fn align(&mut self, alignment: usize) -> &mut Self {
+        self.buf.resize(round_up(self.buf.len(), alignment), 0);
+        self
+    }
+
+    /// Offset the next value would be written at if it had an alignment of 1.
+    pub fn offset(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The packed bytes, padded to a multiple of 16 like the data of a uniform block.
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.align(16);
+        self.buf
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+}
+
+const fn round_up(n: usize, multiple: usize) -> usize {
+    if multiple == 0 {
+        return n;
+    }
+    n.div_ceil(multiple) * multiple
+}
+
+/// Scalar types vectors and matrices can be made of.
+pub trait Std140Scalar: Std140 + glm::Scalar + Copy {}
+
+impl Std140Scalar for GLfloat {}
+impl Std140Scalar for GLdouble {}
+impl Std140Scalar for GLint {}
+impl Std140Scalar for GLuint {}
+
+impl Std140 for GLfloat {
+    const ALIGNMENT: usize = 4;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_bytes(&self.to_ne_bytes());
+    }
+}
+
+impl Std140 for GLdouble {
+    const ALIGNMENT: usize = 8;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_bytes(&self.to_ne_bytes());
+    }
+}
+
+impl Std140 for GLint {
+    const ALIGNMENT: usize = 4;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_bytes(&self.to_ne_bytes());
+    }
+}
+
+impl Std140 for GLuint {
+    const ALIGNMENT: usize = 4;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_bytes(&self.to_ne_bytes());
+    }
+}
+
+/// GLSL booleans take up 4 bytes.
+impl Std140 for bool {
+    const ALIGNMENT: usize = 4;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        writer.write_bytes(&GLuint::from(*self).to_ne_bytes());
+    }
+}
+
+impl<T: Std140Scalar> Std140 for glm::TVec2<T> {
+    const ALIGNMENT: usize = 2 * T::ALIGNMENT;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.iter().for_each(|c| c.write_std140(writer));
+    }
+}
+
+/// Aligned like a four component vector.
+impl<T: Std140Scalar> Std140 for glm::TVec3<T> {
+    const ALIGNMENT: usize = 4 * T::ALIGNMENT;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.iter().for_each(|c| c.write_std140(writer));
+    }
+}
+
+impl<T: Std140Scalar> Std140 for glm::TVec4<T> {
+    const ALIGNMENT: usize = 4 * T::ALIGNMENT;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.iter().for_each(|c| c.write_std140(writer));
+    }
+}
+
+/// Column major matrices are laid out like an array of their column vectors.
+impl<T: Std140Scalar> Std140 for glm::TMat2<T> {
+    const ALIGNMENT: usize = round_up(glm::TVec2::<T>::ALIGNMENT, 16);
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        for column in self.column_iter() {
+            writer.write(&column.into_owned());
+            writer.align(Self::ALIGNMENT);
+        }
+    }
+}
+
+impl<T: Std140Scalar> Std140 for glm::TMat3<T> {
+    const ALIGNMENT: usize = round_up(glm::TVec3::<T>::ALIGNMENT, 16);
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        for column in self.column_iter() {
+            writer.write(&column.into_owned());
+            writer.align(Self::ALIGNMENT);
+        }
+    }
+}
+
+impl<T: Std140Scalar> Std140 for glm::TMat4<T> {
+    const ALIGNMENT: usize = round_up(glm::TVec4::<T>::ALIGNMENT, 16);
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        for column in self.column_iter() {
+            writer.write(&column.into_owned());
+            writer.align(Self::ALIGNMENT);
+        }
+    }
+}
+
+/// Every element starts at a multiple of the element alignment rounded up to 16.
+impl<T: Std140> Std140 for [T] {
+    const ALIGNMENT: usize = round_up(T::ALIGNMENT, 16);
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        for element in self {
+            writer.align(Self::ALIGNMENT);
+            writer.write(element);
+        }
+    }
+}
+
+impl<T: Std140, const N: usize> Std140 for [T; N] {
+    const ALIGNMENT: usize = <[T]>::ALIGNMENT;
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.as_slice().write_std140(writer);
+    }
+}
+
+impl<T: Std140> Std140 for Vec<T> {
+    const ALIGNMENT: usize = <[T]>::ALIGNMENT;
+    const ROUND_UP_SIZE: bool = true;
+
+    fn write_std140(&self, writer: &mut Std140Writer) {
+        self.as_slice().write_std140(writer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floats(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks(4)
+            .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+            .collect()
+    }
+
+    fn pack<T: Std140 + ?Sized>(value: &T) -> Vec<u8> {
+        let mut writer = Std140Writer::new();
+        writer.write(value);
+        writer.as_bytes().to_vec()
+    }
+
+    struct Light {
+        position: glm::Vec3,
+        intensity: f32,
+        color: glm::Vec3,
+    }
+
+    impl Std140 for Light {
+        const ALIGNMENT: usize = struct_alignment(&[glm::Vec3::ALIGNMENT, f32::ALIGNMENT]);
+        const ROUND_UP_SIZE: bool = true;
+
+        fn write_std140(&self, writer: &mut Std140Writer) {
+            writer
+                .write(&self.position)
+                .write(&self.intensity)
+                .write(&self.color);
+        }
+    }
+
+    #[test]
+    fn scalars_and_vectors() {
+        let mut writer = Std140Writer::new();
+        writer.write(&1.0_f32);
+        assert_eq!(writer.offset(), 4);
+        writer.write(&glm::vec2(2.0_f32, 3.0));
+        assert_eq!(writer.offset(), 16);
+        writer.write(&glm::vec3(4.0_f32, 5.0, 6.0));
+        assert_eq!(writer.offset(), 28);
+        // A scalar fits in the padding after a vec3.
+        writer.write(&7.0_f32);
+        assert_eq!(writer.offset(), 32);
+        writer
+            .write(&true)
+            .write(&glm::vec4(8.0_f32, 9.0, 10.0, 11.0));
+        assert_eq!(writer.offset(), 64);
+
+        let bytes = writer.into_bytes();
+        assert_eq!(
+            floats(&bytes),
+            [
+                1.0,
+                0.0,
+                2.0,
+                3.0,
+                4.0,
+                5.0,
+                6.0,
+                7.0,
+                f32::from_bits(1),
+                0.0,
+                0.0,
+                0.0,
+                8.0,
+                9.0,
+                10.0,
+                11.0
+            ]
+        );
+    }
+
+    #[test]
+    fn doubles() {
+        assert_eq!(pack(&glm::DVec2::zeros()).len(), 16);
+        assert_eq!(glm::DVec3::ALIGNMENT, 32);
+
+        let mut writer = Std140Writer::new();
+        writer.write(&1.0_f64).write(&glm::DVec3::zeros());
+        assert_eq!(writer.offset(), 32 + 24);
+    }
+
+    #[test]
+    fn scalar_arrays_use_vec4_stride() {
+        let bytes = pack(&[1.0_f32, 2.0, 3.0]);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(floats(&bytes[16..20]), [2.0]);
+        assert_eq!(floats(&bytes[32..36]), [3.0]);
+
+        let mut writer = Std140Writer::new();
+        writer
+            .write(&vec![glm::vec3(1.0_f32, 1.0, 1.0); 2])
+            .write(&1.0_f32);
+        assert_eq!(writer.offset(), 36);
+    }
+
+    #[test]
+    fn matrices() {
+        let mat3 = glm::mat3(1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0);
+        let bytes = pack(&mat3);
+        assert_eq!(bytes.len(), 48);
+        // Column major, each column padded to a vec4.
+        assert_eq!(
+            floats(&bytes),
+            [1.0, 4.0, 7.0, 0.0, 2.0, 5.0, 8.0, 0.0, 3.0, 6.0, 9.0, 0.0]
+        );
+
+        assert_eq!(pack(&glm::Mat2::identity()).len(), 32);
+        assert_eq!(pack(&glm::Mat4::identity()).len(), 64);
+        assert_eq!(pack(&glm::DMat3::identity()).len(), 96);
+
+        let mut writer = Std140Writer::new();
+        writer.write(&1.0_f32).write(&glm::Mat4::identity());
+        assert_eq!(writer.offset(), 80);
+    }
+
+    fn light() -> Light {
+        Light {
+            position: glm::vec3(1.0, 2.0, 3.0),
+            intensity: 4.0,
+            color: glm::vec3(5.0, 6.0, 7.0),
+        }
+    }
+
+    #[test]
+    fn nested_structs() {
+        assert_eq!(Light::ALIGNMENT, 16);
+        assert_eq!(
+            floats(&pack(&light())),
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 0.0]
+        );
+
+        let mut writer = Std140Writer::new();
+        writer
+            .write(&1.0_f32)
+            .write(&[light(), light()])
+            .write(&2.0_f32);
+        assert_eq!(writer.offset(), 16 + 2 * 32 + 4);
+        assert_eq!(floats(&writer.as_bytes()[16..20]), [1.0]);
+        assert_eq!(floats(&writer.as_bytes()[48..52]), [1.0]);
+    }
+
+    #[test]
+    fn into_bytes_pads_to_vec4() {
+        let mut writer = Std140Writer::new();
+        writer.write(&1_i32);
+        assert_eq!(writer.into_bytes().len(), 16);
+        assert!(Std140Writer::new().into_bytes().is_empty());
+    }
+}
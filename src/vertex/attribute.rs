@@ -0,0 +1,208 @@
+use crate::internal_utils::try_into;
+use gl::types::{GLenum, GLint};
+use half::f16;
+use std::mem;
+
+/// Values of a vertex attribute, `component_size` values per vertex.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeData {
+    F32(Vec<f32>),
+    F16(Vec<f16>),
+    I8(Vec<i8>),
+    U8(Vec<u8>),
+    I16(Vec<i16>),
+    U16(Vec<u16>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+}
+
+/// How the shader sees the values of an attribute.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum AttributeFormat {
+    /// Values are converted to floating point as is.
+    #[default]
+    Float,
+    /// Integer values are mapped to `[0, 1]`, or `[-1, 1]` for signed types, e.g. for colors
+    /// packed into bytes.
+    Normalized,
+    /// Integer values are passed to `int`, `uint`, `ivecN` or `uvecN` shader inputs without
+    /// conversion through `glVertexAttribIPointer` e.g. for bone indices.
+    Integer,
+}
+
+impl AttributeData {
+    /// Number of values, not vertices.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::F32(d) => d.len(),
+            Self::F16(d) => d.len(),
+            Self::I8(d) => d.len(),
+            Self::U8(d) => d.len(),
+            Self::I16(d) => d.len(),
+            Self::U16(d) => d.len(),
+            Self::I32(d) => d.len(),
+            Self::U32(d) => d.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn gl_type(&self) -> GLenum {
+        match self {
+            Self::F32(_) => gl::FLOAT,
+            Self::F16(_) => gl::HALF_FLOAT,
+            Self::I8(_) => gl::BYTE,
+            Self::U8(_) => gl::UNSIGNED_BYTE,
+            Self::I16(_) => gl::SHORT,
+            Self::U16(_) => gl::UNSIGNED_SHORT,
+            Self::I32(_) => gl::INT,
+            Self::U32(_) => gl::UNSIGNED_INT,
+        }
+    }
+
+    /// Size in bytes of a single value.
+    pub fn value_size(&self) -> usize {
+        match self {
+            Self::F32(_) => mem::size_of::<f32>(),
+            Self::F16(_) => mem::size_of::<f16>(),
+            Self::I8(_) => mem::size_of::<i8>(),
+            Self::U8(_) => mem::size_of::<u8>(),
+            Self::I16(_) => mem::size_of::<i16>(),
+            Self::U16(_) => mem::size_of::<u16>(),
+            Self::I32(_) => mem::size_of::<i32>(),
+            Self::U32(_) => mem::size_of::<u32>(),
+        }
+    }
+
+    /// Name of the Rust type of the values.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::F32(_) => "f32",
+            Self::F16(_) => "f16",
+            Self::I8(_) => "i8",
+            Self::U8(_) => "u8",
+            Self::I16(_) => "i16",
+            Self::U16(_) => "u16",
+            Self::I32(_) => "i32",
+            Self::U32(_) => "u32",
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Self::F32(_) | Self::F16(_))
+    }
+
+    /// Overwrites the values starting at value `start` with `values`. Returns `false` without
+    /// changing anything if `values` holds a different type or doesn't fit.
+    pub(crate) fn write_at(&mut self, start: usize, values: &AttributeData) -> bool {
+        if start + values.len() > self.len() {
+            return false;
+        }
+        let range = start..start + values.len();
+        match (self, values) {
+            (Self::F32(d), Self::F32(v)) => d[range].copy_from_slice(v),
+            (Self::F16(d), Self::F16(v)) => d[range].copy_from_slice(v),
+            (Self::I8(d), Self::I8(v)) => d[range].copy_from_slice(v),
+            (Self::U8(d), Self::U8(v)) => d[range].copy_from_slice(v),
+            (Self::I16(d), Self::I16(v)) => d[range].copy_from_slice(v),
+            (Self::U16(d), Self::U16(v)) => d[range].copy_from_slice(v),
+            (Self::I32(d), Self::I32(v)) => d[range].copy_from_slice(v),
+            (Self::U32(d), Self::U32(v)) => d[range].copy_from_slice(v),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Truncates or zero-extends the values to `len` values.
+    pub(crate) fn resize(&mut self, len: usize) {
+        match self {
+            Self::F32(d) => d.resize(len, 0.0),
+            Self::F16(d) => d.resize(len, f16::ZERO),
+            Self::I8(d) => d.resize(len, 0),
+            Self::U8(d) => d.resize(len, 0),
+            Self::I16(d) => d.resize(len, 0),
+            Self::U16(d) => d.resize(len, 0),
+            Self::I32(d) => d.resize(len, 0),
+            Self::U32(d) => d.resize(len, 0),
+        }
+    }
+
+    /// Appends the bytes of the `component_size` values of vertex `vertex` to `out`.
+    pub(crate) fn extend_vertex(&self, vertex: usize, component_size: GLint, out: &mut Vec<u8>) {
+        let components: usize = try_into!(component_size);
+        let range = vertex * components..(vertex + 1) * components;
+        match self {
+            Self::F32(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::F16(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::I8(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::U8(d) => out.extend_from_slice(&d[range]),
+            Self::I16(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::U16(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::I32(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+            Self::U32(d) => d[range].iter().for_each(|v| out.extend(v.to_ne_bytes())),
+        }
+    }
+}
+
+macro_rules! impl_from_values {
+    ($($ty:ty => $variant:ident;)*) => {
+        $(
+            impl From<Vec<$ty>> for AttributeData {
+                fn from(values: Vec<$ty>) -> Self {
+                    Self::$variant(values)
+                }
+            }
+
+            impl From<&[$ty]> for AttributeData {
+                fn from(values: &[$ty]) -> Self {
+                    Self::$variant(values.to_vec())
+                }
+            }
+
+            impl<const N: usize> From<&[$ty; N]> for AttributeData {
+                fn from(values: &[$ty; N]) -> Self {
+                    Self::$variant(values.to_vec())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_values! {
+    f32 => F32;
+    f16 => F16;
+    i8 => I8;
+    u8 => U8;
+    i16 => I16;
+    u16 => U16;
+    i32 => I32;
+    u32 => U32;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_at() {
+        let mut data = AttributeData::from(vec![1u16, 2, 3, 4]);
+        assert!(data.write_at(1, &vec![5u16, 6].into()));
+        assert_eq!(data, AttributeData::U16(vec![1, 5, 6, 4]));
+        assert!(data.write_at(2, &vec![7u16, 8].into()));
+        assert_eq!(data, AttributeData::U16(vec![1, 5, 7, 8]));
+
+        assert!(!data.write_at(3, &vec![9u16, 9].into()));
+        assert!(!data.write_at(0, &vec![9i16].into()));
+        assert_eq!(data, AttributeData::U16(vec![1, 5, 7, 8]));
+    }
+
+    #[test]
+    fn extend_vertex() {
+        let data = AttributeData::from(vec![1i16, -1, 2, -2]);
+        let mut out = Vec::new();
+        data.extend_vertex(1, 2, &mut out);
+        assert_eq!(out, [2i16.to_ne_bytes(), (-2i16).to_ne_bytes()].concat());
+    }
+}
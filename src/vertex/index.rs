@@ -0,0 +1,149 @@
+use crate::error::{Error, Result};
+use gl::types::GLenum;
+
+/// Ends a strip within the indexes of a vertex object built with
+/// [VertexObjectBuilder::primitive_restart](super::VertexObjectBuilder::primitive_restart). It's
+/// uploaded as the largest value of the [IndexType] in use.
+pub const RESTART_INDEX: u32 = u32::MAX;
+
+/// Type of the values of an index buffer. Smaller types take less memory but can refer to fewer
+/// vertices. The largest value of each type is reserved for [RESTART_INDEX].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IndexType {
+    U8,
+    U16,
+    U32,
+}
+
+impl IndexType {
+    /// Smallest type that can refer to each of `num_vertices` vertices.
+    pub fn fitting(num_vertices: usize) -> Self {
+        if num_vertices <= Self::U8.max_value() as usize {
+            Self::U8
+        } else if num_vertices <= Self::U16.max_value() as usize {
+            Self::U16
+        } else {
+            Self::U32
+        }
+    }
+
+    /// Size in bytes of a single index.
+    pub fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+        }
+    }
+
+    /// Largest value of the type, used as the primitive restart index.
+    pub fn max_value(self) -> u32 {
+        match self {
+            Self::U8 => u8::MAX.into(),
+            Self::U16 => u16::MAX.into(),
+            Self::U32 => u32::MAX,
+        }
+    }
+
+    /// Packs `indexes` as values of this type, mapping [RESTART_INDEX] to [IndexType::max_value].
+    /// Indexes must fit in the type.
+    pub(crate) fn pack(self, indexes: &[u32]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(indexes.len() * self.size());
+        for &index in indexes {
+            let index = if index == RESTART_INDEX {
+                self.max_value()
+            } else {
+                index
+            };
+            match self {
+                Self::U8 => bytes.push(index as u8),
+                Self::U16 => bytes.extend((index as u16).to_ne_bytes()),
+                Self::U32 => bytes.extend(index.to_ne_bytes()),
+            }
+        }
+        bytes
+    }
+
+    /// Every index must fit in the type without colliding with its restart index, and
+    /// [RESTART_INDEX] can only be used with primitive restart.
+    pub(crate) fn validate(self, indexes: &[u32], primitive_restart: bool) -> Result<()> {
+        for &index in indexes {
+            if index == RESTART_INDEX {
+                if !primitive_restart {
+                    return Err(Error::InvalidVertexObject(
+                        "indexes contain RESTART_INDEX but primitive restart isn't enabled"
+                            .to_string(),
+                    ));
+                }
+            } else if index >= self.max_value() {
+                return Err(Error::InvalidVertexObject(format!(
+                    "index value {index} doesn't fit in {self:?} indexes"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<IndexType> for GLenum {
+    fn from(value: IndexType) -> GLenum {
+        match value {
+            IndexType::U8 => gl::UNSIGNED_BYTE,
+            IndexType::U16 => gl::UNSIGNED_SHORT,
+            IndexType::U32 => gl::UNSIGNED_INT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fitting() {
+        assert_eq!(IndexType::fitting(1), IndexType::U8);
+        assert_eq!(IndexType::fitting(255), IndexType::U8);
+        assert_eq!(IndexType::fitting(256), IndexType::U16);
+        assert_eq!(IndexType::fitting(65535), IndexType::U16);
+        assert_eq!(IndexType::fitting(65536), IndexType::U32);
+    }
+
+    #[test]
+    fn max_value_is_reserved() {
+        assert!(IndexType::U8.validate(&[0, 254], false).is_ok());
+        assert!(IndexType::U8.validate(&[0, 255], false).is_err());
+        assert!(IndexType::U8.validate(&[0, 255], true).is_err());
+        assert!(IndexType::U16.validate(&[65534], false).is_ok());
+        assert!(IndexType::U16.validate(&[65535], false).is_err());
+        assert!(IndexType::U32.validate(&[u32::MAX - 1], false).is_ok());
+    }
+
+    #[test]
+    fn restart_index() {
+        let indexes = [0, 1, RESTART_INDEX, 2];
+        assert!(IndexType::U8.validate(&indexes, false).is_err());
+        for index_type in [IndexType::U8, IndexType::U16, IndexType::U32] {
+            assert!(index_type.validate(&indexes, true).is_ok());
+        }
+    }
+
+    #[test]
+    fn pack() {
+        let indexes = [1, 258, RESTART_INDEX];
+        assert_eq!(IndexType::U8.pack(&[1, 2, RESTART_INDEX]), [1, 2, 255]);
+        assert_eq!(
+            IndexType::U16.pack(&indexes),
+            [1u16, 258, u16::MAX]
+                .iter()
+                .flat_map(|i| i.to_ne_bytes())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            IndexType::U32.pack(&indexes),
+            [1u32, 258, u32::MAX]
+                .iter()
+                .flat_map(|i| i.to_ne_bytes())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::AttributeFormat;
+use gl::types::{GLbyte, GLenum, GLfloat, GLint, GLshort, GLubyte, GLuint, GLushort};
+use half::f16;
+use nalgebra_glm as glm;
+
+/// A vertex type whose values can be uploaded as is into an interleaved vertex buffer with
+/// [VertexObjectBuilder::vertices](super::VertexObjectBuilder::vertices). Implement it with
+/// `#[derive(Vertex)]`; see [gloam_derive::Vertex].
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` and [Vertex::attributes] must only describe data that lies
+/// within `Self`.
+///
+/// Attribute offsets must be multiples of the size of their values, and of at least 4 bytes, the
+/// same alignment vertex objects built from separate attributes use.
+pub unsafe trait Vertex: Copy {
+    fn attributes() -> Vec<AttributeLayout>;
+}
+
+/// Where and how a vertex attribute is stored within a [Vertex].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeLayout {
+    pub name: String,
+    pub components: GLint,
+    pub gl_type: GLenum,
+    pub format: AttributeFormat,
+    /// Offset in bytes from the start of the vertex.
+    pub offset: usize,
+}
+
+impl AttributeLayout {
+    /// Size in bytes of a single value of [AttributeLayout::gl_type].
+    pub fn value_size(&self) -> usize {
+        match self.gl_type {
+            gl::BYTE | gl::UNSIGNED_BYTE => 1,
+            gl::SHORT | gl::UNSIGNED_SHORT | gl::HALF_FLOAT => 2,
+            gl::DOUBLE => 8,
+            _ => 4,
+        }
+    }
+}
+
+/// Rust types that can be the type of a [Vertex] field.
+pub trait AttributeType {
+    const COMPONENTS: GLint;
+    const GL_TYPE: GLenum;
+}
+
+macro_rules! impl_attribute_type {
+    ($($ty:ty => $gl_type:expr;)*) => {
+        $(
+            impl AttributeType for $ty {
+                const COMPONENTS: GLint = 1;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for [$ty; 1] {
+                const COMPONENTS: GLint = 1;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for [$ty; 2] {
+                const COMPONENTS: GLint = 2;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for [$ty; 3] {
+                const COMPONENTS: GLint = 3;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for [$ty; 4] {
+                const COMPONENTS: GLint = 4;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for glm::TVec2<$ty> {
+                const COMPONENTS: GLint = 2;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for glm::TVec3<$ty> {
+                const COMPONENTS: GLint = 3;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+
+            impl AttributeType for glm::TVec4<$ty> {
+                const COMPONENTS: GLint = 4;
+                const GL_TYPE: GLenum = $gl_type;
+            }
+        )*
+    };
+}
+
+impl_attribute_type! {
+    GLfloat => gl::FLOAT;
+    f16 => gl::HALF_FLOAT;
+    GLbyte => gl::BYTE;
+    GLubyte => gl::UNSIGNED_BYTE;
+    GLshort => gl::SHORT;
+    GLushort => gl::UNSIGNED_SHORT;
+    GLint => gl::INT;
+    GLuint => gl::UNSIGNED_INT;
+}
@@ -3,14 +3,34 @@ use crate::{
     context::GLContext,
     error::{Error, Result},
     internal_utils::{as_gl_bool, try_into},
-    object::{GLObject, GLObjectDescriptor},
+    object::Handle,
+    shader::program::Program,
 };
-use gl::types::{GLenum, GLint, GLuint};
-use std::{ffi::c_void, marker::PhantomData, mem, ops::Drop};
+use gl::types::{GLenum, GLint, GLsizei, GLuint};
+use std::{
+    ffi::c_void,
+    marker::PhantomData,
+    mem,
+    ops::{Drop, Range},
+};
+
+pub mod attribute;
+pub use attribute::{AttributeData, AttributeFormat};
+pub use half::f16;
+
+pub mod index;
+pub use index::{IndexType, RESTART_INDEX};
+
+pub mod layout;
+pub use gloam_derive::Vertex;
+pub use layout::{AttributeLayout, AttributeType, Vertex};
 
 pub mod primitives;
 pub use primitives::Primitive;
 
+pub mod submesh;
+pub use submesh::Submesh;
+
 pub mod usage;
 pub use usage::Usage;
 
@@ -19,10 +39,14 @@ pub struct VertexObject {
     pub(crate) vertex_array_object: GLuint,
     pub(crate) vertex_buffer_object: GLuint,
     pub(crate) index_buffer_object: Option<IndexObject>,
+    pub(crate) instance_buffer_object: Option<InstanceObject>,
     pub(crate) attributes: Vec<VertexAttribute>,
     pub(crate) num_vertices: GLint,
+    /// Size in bytes of a single vertex in the vertex buffer.
+    pub(crate) stride: GLint,
     pub(crate) primitive: Primitive,
     pub(crate) usage: Usage,
+    pub(crate) submeshes: Vec<(String, Submesh)>,
 }
 
 #[derive(derivative::Derivative)]
@@ -30,76 +54,179 @@ pub struct VertexObject {
 pub struct VertexAttribute {
     pub(crate) name: String,
     pub(crate) component_size: GLint,
+    /// `None` for attributes uploaded from interleaved [Vertex] data.
     #[derivative(Debug = "ignore")]
-    pub(crate) data: Vec<f32>,
-    pub(crate) normalized: bool,
+    pub(crate) data: Option<AttributeData>,
+    pub(crate) format: AttributeFormat,
+    pub(crate) gl_type: GLenum,
+    /// Offset in bytes within a vertex.
+    pub(crate) offset: usize,
+    /// Number of instances that use each value; 0 for per-vertex attributes.
+    pub(crate) divisor: GLuint,
 }
 
 #[derive(derivative::Derivative)]
 #[derivative(Debug)]
 pub struct IndexObject {
     pub(crate) gl_object_id: GLuint,
+    /// Indexes as given, with [RESTART_INDEX] marking restarts, whatever the [IndexType].
     #[derivative(Debug = "ignore")]
     pub(crate) indexes: Vec<u32>,
+    pub(crate) index_type: IndexType,
+    pub(crate) primitive_restart: bool,
+}
+
+/// Per-instance attributes, interleaved in a buffer of their own.
+#[derive(Debug)]
+pub struct InstanceObject {
+    pub(crate) gl_object_id: GLuint,
+    pub(crate) attributes: Vec<VertexAttribute>,
+    /// Number of values of each attribute, i.e. of instances when every divisor is 1.
+    pub(crate) len: GLint,
+    /// Size in bytes of the values of a single instance.
+    pub(crate) stride: GLint,
 }
 
-pub struct VertexObjectBuilder<T> {
+pub struct VertexObjectBuilder<'a, T> {
     pub(crate) num_vertices: GLint,
     pub(crate) attributes: Vec<VertexAttribute>,
+    pub(crate) instance_attributes: Vec<VertexAttribute>,
+    pub(crate) interleaved: Option<InterleavedData<'a>>,
     pub(crate) indexes: Option<Vec<u32>>,
+    pub(crate) index_type: Option<IndexType>,
+    pub(crate) primitive_restart: bool,
+    pub(crate) submeshes: Vec<(String, Submesh)>,
     pub(crate) usage: Usage,
     pub(crate) primitive: Primitive,
     pub(crate) state: PhantomData<T>,
 }
 
-pub type VOBuilder = VertexObjectBuilder<VOBInit>;
+/// Vertices borrowed from the slice passed to [VertexObjectBuilder::vertices].
+pub(crate) struct InterleavedData<'a> {
+    ptr: *const c_void,
+    size: usize,
+    stride: GLint,
+    vertices: PhantomData<&'a [u8]>,
+}
+
+pub type VOBuilder<'a> = VertexObjectBuilder<'a, VOBInit>;
 
 pub struct VOBInit;
 pub struct VOBAttr;
 
-impl<T> VertexObjectBuilder<T> {
-    pub fn new(primitive: Primitive, usage: Usage) -> VertexObjectBuilder<VOBInit> {
+impl<'a, T> VertexObjectBuilder<'a, T> {
+    pub fn new(primitive: Primitive, usage: Usage) -> VertexObjectBuilder<'a, VOBInit> {
         VertexObjectBuilder {
             usage,
             primitive,
             attributes: Vec::new(),
+            instance_attributes: Vec::new(),
+            interleaved: None,
             indexes: None,
+            index_type: None,
+            primitive_restart: false,
+            submeshes: Vec::new(),
             num_vertices: 0,
             state: PhantomData {},
         }
     }
 }
 
-impl VertexObjectBuilder<VOBInit> {
-    pub fn attribute(
-        mut self,
-        name: &str,
-        component_size: GLint,
-        data: &[f32],
-    ) -> Result<VertexObjectBuilder<VOBAttr>> {
-        if data.is_empty() {
+impl<'a> VertexObjectBuilder<'a, VOBInit> {
+    /// Uses `vertices` as the vertex buffer as is, with one attribute per field of `V` as
+    /// described by [Vertex::attributes]. Nothing is copied; the data is uploaded straight from
+    /// `vertices` when the vertex object is built. Further attributes can't be added. Fails if an
+    /// attribute isn't aligned as described by [Vertex].
+    pub fn vertices<V: Vertex>(
+        self,
+        vertices: &'a [V],
+    ) -> Result<VertexObjectBuilder<'a, VOBAttr>> {
+        if vertices.is_empty() {
             return Err(Error::InvalidVertexObject(
-                "vertex attribute must have data".to_string(),
+                "vertex object must have vertices".to_string(),
             ));
         }
-        let num_values: GLint = try_into!(data.len());
-        if num_values % component_size != 0 {
+        let layouts = V::attributes();
+        if let Some(layout) = layouts
+            .iter()
+            .find(|layout| layout.offset % attribute_alignment(layout.value_size()) != 0)
+        {
             return Err(Error::InvalidVertexObject(format!(
-                "length of data ({num_values}) must be evenly divisible by component size ({component_size})"
+                "attribute '{}' at byte offset {} isn't aligned to {} bytes",
+                layout.name,
+                layout.offset,
+                attribute_alignment(layout.value_size())
             )));
         }
-        let num_vertices = num_values / component_size;
+        let attributes = layouts
+            .into_iter()
+            .map(|layout| VertexAttribute {
+                name: layout.name,
+                component_size: layout.components,
+                data: None,
+                format: layout.format,
+                gl_type: layout.gl_type,
+                offset: layout.offset,
+                divisor: 0,
+            })
+            .collect::<Vec<_>>();
+        if attributes.is_empty() {
+            return Err(Error::InvalidVertexObject(
+                "vertex type has no attributes".to_string(),
+            ));
+        }
 
-        self.attributes.push(VertexAttribute {
-            component_size,
-            name: name.to_string(),
-            data: data.to_vec(),
-            normalized: false,
-        });
+        Ok(VertexObjectBuilder {
+            attributes,
+            instance_attributes: self.instance_attributes,
+            num_vertices: try_into!(vertices.len()),
+            interleaved: Some(InterleavedData {
+                ptr: vertices.as_ptr() as *const c_void,
+                size: mem::size_of_val(vertices),
+                stride: try_into!(mem::size_of::<V>()),
+                vertices: PhantomData,
+            }),
+            indexes: self.indexes,
+            index_type: self.index_type,
+            primitive_restart: self.primitive_restart,
+            submeshes: self.submeshes,
+            usage: self.usage,
+            primitive: self.primitive,
+            state: PhantomData {},
+        })
+    }
+
+    pub fn attribute(
+        self,
+        name: &str,
+        component_size: GLint,
+        data: &[f32],
+    ) -> Result<VertexObjectBuilder<'a, VOBAttr>> {
+        self.attribute_data(name, component_size, data, AttributeFormat::Float)
+    }
+
+    /// Adds an attribute of any of the types supported by [AttributeData], e.g. `&[u8]` colors
+    /// with [AttributeFormat::Normalized] or `&[u32]` bone indices with
+    /// [AttributeFormat::Integer].
+    pub fn attribute_data(
+        mut self,
+        name: &str,
+        component_size: GLint,
+        data: impl Into<AttributeData>,
+        format: AttributeFormat,
+    ) -> Result<VertexObjectBuilder<'a, VOBAttr>> {
+        let attribute = VertexAttribute::new(name, component_size, data.into(), format)?;
+        let num_vertices = attribute.num_vertices();
+        self.attributes.push(attribute);
         Ok(VertexObjectBuilder {
             num_vertices,
             attributes: self.attributes,
+            instance_attributes: self.instance_attributes,
+            interleaved: None,
             indexes: self.indexes,
+            index_type: self.index_type,
+            primitive_restart: self.primitive_restart,
+            submeshes: self.submeshes,
             usage: self.usage,
             primitive: self.primitive,
             state: PhantomData {},
@@ -107,62 +234,117 @@ impl VertexObjectBuilder<VOBInit> {
     }
 }
 
-impl VertexObjectBuilder<VOBAttr> {
-    pub fn attribute(mut self, name: &str, component_size: GLint, data: &[f32]) -> Result<Self> {
-        if data.is_empty() {
+impl<'a> VertexObjectBuilder<'a, VOBAttr> {
+    pub fn attribute(self, name: &str, component_size: GLint, data: &[f32]) -> Result<Self> {
+        self.attribute_data(name, component_size, data, AttributeFormat::Float)
+    }
+
+    /// See [VertexObjectBuilder::attribute_data].
+    pub fn attribute_data(
+        mut self,
+        name: &str,
+        component_size: GLint,
+        data: impl Into<AttributeData>,
+        format: AttributeFormat,
+    ) -> Result<Self> {
+        if self.interleaved.is_some() {
             return Err(Error::InvalidVertexObject(
-                "vertex attribute must have data".to_string(),
+                "can't add attributes to a vertex object built from interleaved vertices"
+                    .to_string(),
             ));
         }
-
-        let num_values: GLint = try_into!(data.len());
-        let new_attr_num_vertices = num_values / component_size;
-
-        if self.num_vertices != new_attr_num_vertices {
+        let attribute = VertexAttribute::new(name, component_size, data.into(), format)?;
+        if self.num_vertices != attribute.num_vertices() {
             return Err(Error::InvalidVertexObject(format!(
                 "number of vertices for attribute '{name}' doesn't match number of vertices for previous attributes"
             )));
         }
-        self.attributes.push(VertexAttribute {
-            component_size,
-            name: name.to_string(),
-            data: data.to_vec(),
-            normalized: false,
-        });
+        self.attributes.push(attribute);
         Ok(self)
     }
 
-    pub fn indexes(mut self, indexes: &[u32]) -> Result<Self> {
-        let first_attr = self.attributes.first().unwrap();
-        let data_len: GLint = try_into!(first_attr.data.len());
-        let num_vertices = data_len / first_attr.component_size;
-
-        let max_index = indexes.iter().max().copied().unwrap_or_default();
-        if max_index > try_into!(num_vertices - 1) {
+    /// Adds an attribute whose values change per instance rather than per vertex, advancing to
+    /// the next value every `divisor` instances. Instance attributes are stored in a buffer of
+    /// their own that can be updated with [GLContext::try_update_instance_attribute] and must all
+    /// have the same number of values.
+    pub fn instance_attribute(
+        mut self,
+        name: &str,
+        component_size: GLint,
+        data: impl Into<AttributeData>,
+        format: AttributeFormat,
+        divisor: GLuint,
+    ) -> Result<Self> {
+        if divisor == 0 {
             return Err(Error::InvalidVertexObject(format!(
-                "index value {max_index} out of bounds with number of vertices, {num_vertices}"
+                "divisor of instance attribute '{name}' must be at least 1"
             )));
         }
+        let mut attribute = VertexAttribute::new(name, component_size, data.into(), format)?;
+        attribute.divisor = divisor;
+        let len = attribute.num_vertices();
+        if let Some(first) = self.instance_attributes.first() {
+            if first.num_vertices() != len {
+                return Err(Error::InvalidVertexObject(format!(
+                    "instance attribute '{name}' has {len} values but previous instance attributes have {}",
+                    first.num_vertices()
+                )));
+            }
+        }
+        self.instance_attributes.push(attribute);
+        Ok(self)
+    }
+
+    pub fn indexes(mut self, indexes: &[u32]) -> Result<Self> {
+        validate_indexes(indexes, self.num_vertices)?;
         self.indexes = Some(indexes.to_vec());
         Ok(self)
     }
 
+    /// Type of the values of the index buffer. Defaults to the smallest type that can refer to
+    /// every vertex; see [IndexType::fitting].
+    pub fn index_type(mut self, index_type: IndexType) -> Self {
+        self.index_type = Some(index_type);
+        self
+    }
+
+    /// Treats [RESTART_INDEX] in the indexes as the end of a primitive, e.g. so that a single
+    /// [Primitive::TriangleStrip] index buffer can hold many strips.
+    pub fn primitive_restart(mut self) -> Self {
+        self.primitive_restart = true;
+        self
+    }
+
+    /// Names the range `submesh` of the vertex object so that a [Mesh](crate::mesh::Mesh) can
+    /// draw only that range with [Mesh::with_submesh](crate::mesh::Mesh::with_submesh). Adding
+    /// a submesh with the name of an existing one replaces it. Ranges are validated on build.
+    pub fn submesh(mut self, name: &str, submesh: Submesh) -> Self {
+        self.submeshes.retain(|(n, _)| n != name);
+        self.submeshes.push((name.to_string(), submesh));
+        self
+    }
+
     pub fn build(
         self,
         ctx: &mut GLContext,
-        program: GLObjectDescriptor,
-    ) -> Result<GLObjectDescriptor> {
+        program: Handle<Program>,
+    ) -> Result<Handle<VertexObject>> {
         unsafe { self.build_impl(ctx, program) }
     }
 
     unsafe fn build_impl(
         self,
         ctx: &mut GLContext,
-        program: GLObjectDescriptor,
-    ) -> Result<GLObjectDescriptor> {
+        program: Handle<Program>,
+    ) -> Result<Handle<VertexObject>> {
         let VertexObjectBuilder {
-            attributes,
+            mut attributes,
+            mut instance_attributes,
+            interleaved,
             indexes,
+            index_type,
+            primitive_restart,
+            submeshes,
             usage,
             primitive,
             num_vertices,
@@ -175,68 +357,110 @@ impl VertexObjectBuilder<VOBAttr> {
             ));
         }
 
-        let mut buffer = Vec::with_capacity(attributes.iter().map(|a| a.data.len()).sum::<usize>());
-        let size_of_f32: GLint = try_into!(mem::size_of::<f32>());
-        let stride = size_of_f32 * attributes.iter().map(|a| a.component_size).sum::<GLint>();
-        let usage_enum = GLenum::from(usage);
-
-        let mut chunk_iters = attributes
-            .iter()
-            .map(|attr| attr.data.chunks(try_into!(attr.component_size)))
-            .collect::<Vec<_>>();
+        primitive.validate(ctx.gl_version())?;
+        let index_type = index_type.unwrap_or_else(|| IndexType::fitting(try_into!(num_vertices)));
+        if let Some(indexes) = &indexes {
+            index_type.validate(indexes, primitive_restart)?;
+        }
 
-        for i in (0..chunk_iters.len()).cycle() {
-            let Some(chunk) = chunk_iters.get_mut(i).and_then(|c| c.next()) else {
-                break;
+        let reflected = ctx.get_program(program)?;
+        for attribute in attributes.iter().chain(&instance_attributes) {
+            let active = reflected
+                .active_attribute(&attribute.name)
+                .ok_or_else(|| Error::InactiveAttribute(attribute.name.clone()))?;
+            attribute.validate_format()?;
+            let components = match attribute.format {
+                AttributeFormat::Integer => active.integer_components(),
+                AttributeFormat::Float | AttributeFormat::Normalized => active.float_components(),
             };
-            buffer.extend_from_slice(chunk);
+            let fits = components.is_some_and(|components| attribute.component_size <= components);
+            if !fits {
+                return Err(Error::AttributeTypeMismatch {
+                    name: attribute.name.clone(),
+                    expected: active.type_name(),
+                    found: attribute.component_size,
+                });
+            }
         }
 
+        let usage_enum = GLenum::from(usage);
+
         let mut vbo = 0;
         gl::GenBuffers(1, &mut vbo);
         gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
-        gl::BufferData(
-            gl::ARRAY_BUFFER,
-            try_into!(mem::size_of_val(buffer.as_slice())),
-            buffer.as_ptr() as *const c_void,
-            usage_enum,
-        );
+
+        let stride = match interleaved {
+            Some(vertices) => {
+                gl::BufferData(
+                    gl::ARRAY_BUFFER,
+                    try_into!(vertices.size),
+                    vertices.ptr,
+                    usage_enum,
+                );
+                vertices.stride
+            }
+            None => {
+                let stride = layout_attributes(&mut attributes);
+                let buffer = interleave(&attributes, stride, 0..try_into!(num_vertices));
+                gl::BufferData(
+                    gl::ARRAY_BUFFER,
+                    try_into!(buffer.len()),
+                    buffer.as_ptr() as *const c_void,
+                    usage_enum,
+                );
+                try_into!(stride)
+            }
+        };
 
         let mut vao = 0;
         gl::GenVertexArrays(1, &mut vao);
         gl::BindVertexArray(vao);
 
-        let mut byte_offset = 0;
-
         ctx.try_use_program(program)?;
 
-        for attribute in &attributes {
-            let attr_loc = ctx.try_get_attrib_loc(program, &attribute.name)?;
-            gl::VertexAttribPointer(
-                attr_loc,
-                attribute.component_size,
-                gl::FLOAT,
-                as_gl_bool(attribute.normalized),
-                stride,
-                byte_offset as *const c_void,
+        set_attribute_pointers(ctx, program, &attributes, stride)?;
+
+        let instance_buffer_object = if instance_attributes.is_empty() {
+            None
+        } else {
+            let stride = layout_attributes(&mut instance_attributes);
+            let len = instance_attributes[0].num_vertices();
+            let buffer = interleave(&instance_attributes, stride, 0..try_into!(len));
+            let mut instance_vbo = 0;
+            gl::GenBuffers(1, &mut instance_vbo);
+            gl::BindBuffer(gl::ARRAY_BUFFER, instance_vbo);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                try_into!(buffer.len()),
+                buffer.as_ptr() as *const c_void,
+                usage_enum,
             );
-            gl::EnableVertexAttribArray(attr_loc);
-            byte_offset += size_of_f32 * attribute.component_size;
-        }
+            let stride = try_into!(stride);
+            set_attribute_pointers(ctx, program, &instance_attributes, stride)?;
+            Some(InstanceObject {
+                len,
+                stride,
+                attributes: instance_attributes,
+                gl_object_id: instance_vbo,
+            })
+        };
 
         let index_buffer_object = indexes.map(|indexes| {
             let mut ebo = 0;
             gl::GenBuffers(1, &mut ebo);
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+            let packed = index_type.pack(&indexes);
             gl::BufferData(
                 gl::ELEMENT_ARRAY_BUFFER,
-                try_into!(mem::size_of_val(indexes.as_slice())),
-                indexes.as_ptr() as *const c_void,
+                try_into!(packed.len()),
+                packed.as_ptr() as *const c_void,
                 usage_enum,
             );
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
             IndexObject {
                 indexes,
+                index_type,
+                primitive_restart,
                 gl_object_id: ebo,
             }
         });
@@ -245,17 +469,425 @@ impl VertexObjectBuilder<VOBAttr> {
         gl::BindBuffer(gl::ARRAY_BUFFER, 0);
         gl::BindVertexArray(0);
 
-        let vertex_object = VertexObject {
+        let mut vertex_object = VertexObject {
             num_vertices,
+            stride,
             attributes,
             primitive,
             usage,
             index_buffer_object,
+            instance_buffer_object,
             vertex_array_object: vao,
             vertex_buffer_object: vbo,
+            submeshes: Vec::new(),
+        };
+        for (name, submesh) in submeshes {
+            vertex_object.set_submesh(&name, submesh)?;
+        }
+        let handle = ctx.register(vertex_object);
+        Ok(handle)
+    }
+}
+
+impl VertexObject {
+    /// Overwrites the values of attribute `name` starting at vertex `first_vertex`, both
+    /// CPU-side and in the vertex buffer.
+    pub(crate) fn update_attribute(
+        &mut self,
+        name: &str,
+        first_vertex: usize,
+        data: AttributeData,
+    ) -> Result<()> {
+        let attribute = self
+            .attributes
+            .iter_mut()
+            .find(|attribute| attribute.name == name)
+            .ok_or_else(|| {
+                Error::InvalidVertexObject(format!("vertex object has no attribute '{name}'"))
+            })?;
+        let vertices = attribute.write(first_vertex, self.num_vertices, &data)?;
+        upload_range(
+            self.vertex_buffer_object,
+            &self.attributes,
+            self.stride,
+            vertices,
+        );
+        Ok(())
+    }
+
+    /// Same as [VertexObject::update_attribute] for the instance attribute `name`, starting at
+    /// value `first`.
+    pub(crate) fn update_instance_attribute(
+        &mut self,
+        name: &str,
+        first: usize,
+        data: AttributeData,
+    ) -> Result<()> {
+        let instances = self.instance_buffer_object.as_mut().and_then(|instances| {
+            let idx = instances.attributes.iter().position(|a| a.name == name)?;
+            Some((instances, idx))
+        });
+        let Some((instances, idx)) = instances else {
+            return Err(Error::InvalidVertexObject(format!(
+                "vertex object has no instance attribute '{name}'"
+            )));
+        };
+        let range = instances.attributes[idx].write(first, instances.len, &data)?;
+        upload_range(
+            instances.gl_object_id,
+            &instances.attributes,
+            instances.stride,
+            range,
+        );
+        Ok(())
+    }
+
+    /// Reallocates the instance buffer to hold `len` values per instance attribute. Existing
+    /// values are kept and new ones are zeroed.
+    pub(crate) fn resize_instances(&mut self, len: usize) -> Result<()> {
+        let Some(instances) = self.instance_buffer_object.as_mut() else {
+            return Err(Error::InvalidVertexObject(
+                "vertex object has no instance attributes".to_string(),
+            ));
+        };
+        if len == 0 {
+            return Err(Error::InvalidVertexObject(
+                "can't resize instance attributes to zero values".to_string(),
+            ));
+        }
+        for attribute in &mut instances.attributes {
+            let components: usize = try_into!(attribute.component_size);
+            if let Some(data) = attribute.data.as_mut() {
+                data.resize(len * components);
+            }
+        }
+        let buffer = interleave(&instances.attributes, try_into!(instances.stride), 0..len);
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, instances.gl_object_id);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                try_into!(buffer.len()),
+                buffer.as_ptr() as *const c_void,
+                self.usage.into(),
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        }
+        instances.len = try_into!(len);
+        Ok(())
+    }
+
+    /// Number of instances covered by the instance attributes, taking their divisors into
+    /// account, or `None` if there are none. Saturates at [GLsizei::MAX], the most instances a
+    /// single draw call can render.
+    pub fn num_instances(&self) -> Option<GLsizei> {
+        let instances = self.instance_buffer_object.as_ref()?;
+        instances
+            .attributes
+            .iter()
+            .map(|attribute| instances_covered(instances.len, attribute.divisor))
+            .min()
+    }
+
+    /// Overwrites the vertices starting at vertex `first_vertex` of a vertex object built from
+    /// interleaved vertices of type `V`.
+    pub(crate) fn update_vertices<V: Vertex>(
+        &self,
+        first_vertex: usize,
+        vertices: &[V],
+    ) -> Result<()> {
+        self.ensure_layout::<V>()?;
+        let num_vertices: usize = try_into!(self.num_vertices);
+        if first_vertex + vertices.len() > num_vertices {
+            return Err(Error::InvalidVertexObject(format!(
+                "updating {} vertices from vertex {first_vertex} overflows vertex object of {num_vertices} vertices",
+                vertices.len()
+            )));
+        }
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vertex_buffer_object);
+            gl::BufferSubData(
+                gl::ARRAY_BUFFER,
+                try_into!(first_vertex * mem::size_of::<V>()),
+                try_into!(mem::size_of_val(vertices)),
+                vertices.as_ptr() as *const c_void,
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        }
+        Ok(())
+    }
+
+    /// Reallocates the vertex buffer to hold `num_vertices` vertices. Existing vertices are
+    /// kept, truncated if the buffer shrinks, and new vertices are zeroed.
+    pub(crate) fn resize(&mut self, num_vertices: usize) -> Result<()> {
+        if num_vertices == 0 {
+            return Err(Error::InvalidVertexObject(
+                "can't resize a vertex object to zero vertices".to_string(),
+            ));
+        }
+        if let Some(max_index) = self
+            .index_buffer_object
+            .as_ref()
+            .and_then(|ibo| ibo.max_index())
+            .filter(|max_index| *max_index as usize >= num_vertices)
+        {
+            return Err(Error::InvalidVertexObject(format!(
+                "can't resize to {num_vertices} vertices while indexes refer to vertex {max_index}"
+            )));
+        }
+        let ibo = self.index_buffer_object.as_ref();
+        self.validate_submeshes(
+            num_vertices,
+            ibo.map(|ibo| ibo.indexes.as_slice()),
+            ibo.is_some_and(|ibo| ibo.primitive_restart),
+        )?;
+
+        let stride: usize = try_into!(self.stride);
+        let buffer = if self.is_interleaved() {
+            // Only the GPU has the vertices so read back those we keep.
+            let old_num_vertices: usize = try_into!(self.num_vertices);
+            let mut buffer = vec![0u8; num_vertices * stride];
+            unsafe {
+                gl::BindBuffer(gl::ARRAY_BUFFER, self.vertex_buffer_object);
+                gl::GetBufferSubData(
+                    gl::ARRAY_BUFFER,
+                    0,
+                    try_into!(old_num_vertices.min(num_vertices) * stride),
+                    buffer.as_mut_ptr() as *mut c_void,
+                );
+            }
+            buffer
+        } else {
+            for attribute in &mut self.attributes {
+                let components: usize = try_into!(attribute.component_size);
+                if let Some(data) = attribute.data.as_mut() {
+                    data.resize(num_vertices * components);
+                }
+            }
+            interleave(&self.attributes, stride, 0..num_vertices)
+        };
+
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vertex_buffer_object);
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                try_into!(buffer.len()),
+                buffer.as_ptr() as *const c_void,
+                self.usage.into(),
+            );
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        }
+        self.num_vertices = try_into!(num_vertices);
+        Ok(())
+    }
+
+    /// Replaces the index buffer, creating one if the vertex object didn't have any. The
+    /// [IndexType] and primitive restart setting of an existing index buffer are kept. Leaves the
+    /// new index buffer bound to `GL_ELEMENT_ARRAY_BUFFER`.
+    pub(crate) fn replace_indexes(&mut self, indexes: &[u32]) -> Result<()> {
+        validate_indexes(indexes, self.num_vertices)?;
+        let (index_type, primitive_restart) = match &self.index_buffer_object {
+            Some(ibo) => (ibo.index_type, ibo.primitive_restart),
+            None => (IndexType::fitting(try_into!(self.num_vertices)), false),
+        };
+        index_type.validate(indexes, primitive_restart)?;
+        self.validate_submeshes(
+            try_into!(self.num_vertices),
+            Some(indexes),
+            primitive_restart,
+        )?;
+
+        let ibo = self.index_buffer_object.get_or_insert_with(|| {
+            let mut ebo = 0;
+            unsafe { gl::GenBuffers(1, &mut ebo) };
+            IndexObject {
+                index_type,
+                primitive_restart,
+                gl_object_id: ebo,
+                indexes: Vec::new(),
+            }
+        });
+        let packed = index_type.pack(indexes);
+        unsafe {
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ibo.gl_object_id);
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                try_into!(packed.len()),
+                packed.as_ptr() as *const c_void,
+                self.usage.into(),
+            );
+        }
+        ibo.indexes = indexes.to_vec();
+        Ok(())
+    }
+
+    /// Adds or replaces the submesh `name`, which must lie within the index buffer, or the
+    /// vertices if there is none. Submeshes are checked whenever the vertices or indexes change
+    /// so that they can be drawn without further checks.
+    pub(crate) fn set_submesh(&mut self, name: &str, submesh: Submesh) -> Result<()> {
+        let ibo = self.index_buffer_object.as_ref();
+        submesh.validate(
+            name,
+            try_into!(self.num_vertices),
+            ibo.map(|ibo| ibo.indexes.as_slice()),
+            ibo.is_some_and(|ibo| ibo.primitive_restart),
+        )?;
+        match self.submeshes.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = submesh,
+            None => self.submeshes.push((name.to_string(), submesh)),
+        }
+        Ok(())
+    }
+
+    /// Removes the submesh `name`, returning it if it existed.
+    pub(crate) fn remove_submesh(&mut self, name: &str) -> Option<Submesh> {
+        let idx = self.submeshes.iter().position(|(n, _)| n == name)?;
+        Some(self.submeshes.remove(idx).1)
+    }
+
+    pub fn get_submesh(&self, name: &str) -> Result<&Submesh> {
+        self.submeshes
+            .iter()
+            .find_map(|(n, submesh)| (n == name).then_some(submesh))
+            .ok_or_else(|| Error::SubmeshNotFound(name.to_string()))
+    }
+
+    /// Names of the submeshes of this vertex object.
+    pub fn submeshes(&self) -> impl Iterator<Item = &str> {
+        self.submeshes.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Checks that every submesh lies within `num_vertices` vertices and `indexes`, before they
+    /// replace the current ones.
+    fn validate_submeshes(
+        &self,
+        num_vertices: usize,
+        indexes: Option<&[u32]>,
+        primitive_restart: bool,
+    ) -> Result<()> {
+        for (name, submesh) in &self.submeshes {
+            submesh.validate(name, num_vertices, indexes, primitive_restart)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the vertex buffer was uploaded from [Vertex] values rather than per attribute
+    /// data, in which case there is no CPU-side copy of the vertices.
+    pub fn is_interleaved(&self) -> bool {
+        self.attributes
+            .iter()
+            .all(|attribute| attribute.data.is_none())
+    }
+
+    fn ensure_layout<V: Vertex>(&self) -> Result<()> {
+        let matches = self.is_interleaved()
+            && mem::size_of::<V>() == try_into!(self.stride)
+            && V::attributes().len() == self.attributes.len()
+            && V::attributes().iter().all(|layout| {
+                self.attributes.iter().any(|attribute| {
+                    attribute.name == layout.name
+                        && attribute.component_size == layout.components
+                        && attribute.gl_type == layout.gl_type
+                        && attribute.format == layout.format
+                        && attribute.offset == layout.offset
+                })
+            });
+        if !matches {
+            return Err(Error::InvalidVertexObject(format!(
+                "vertex object wasn't built from vertices of type {}",
+                std::any::type_name::<V>()
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl VertexAttribute {
+    fn new(
+        name: &str,
+        component_size: GLint,
+        data: AttributeData,
+        format: AttributeFormat,
+    ) -> Result<Self> {
+        if data.is_empty() {
+            return Err(Error::InvalidVertexObject(
+                "vertex attribute must have data".to_string(),
+            ));
+        }
+        if !(1..=4).contains(&component_size) {
+            return Err(Error::InvalidVertexObject(format!(
+                "component size of attribute '{name}' must be between 1 and 4, not {component_size}"
+            )));
+        }
+        let num_values: GLint = try_into!(data.len());
+        if num_values % component_size != 0 {
+            return Err(Error::InvalidVertexObject(format!(
+                "length of data ({num_values}) must be evenly divisible by component size ({component_size})"
+            )));
+        }
+        let attribute = Self {
+            component_size,
+            format,
+            name: name.to_string(),
+            gl_type: data.gl_type(),
+            data: Some(data),
+            offset: 0,
+            divisor: 0,
+        };
+        attribute.validate_format()?;
+        Ok(attribute)
+    }
+
+    /// Overwrites the values of the `len` elements, i.e. vertices or instances, of this
+    /// attribute starting at element `first` CPU-side. Returns the range of elements that changed.
+    fn write(&mut self, first: usize, len: GLint, data: &AttributeData) -> Result<Range<usize>> {
+        let name = &self.name;
+        let Some(values) = self.data.as_mut() else {
+            return Err(Error::InvalidVertexObject(format!(
+                "attribute '{name}' was uploaded from interleaved vertices; update those instead"
+            )));
         };
-        let obj_desc = ctx.register_object(GLObject::VertexObject(vertex_object));
-        Ok(obj_desc)
+        if data.gl_type() != values.gl_type() {
+            return Err(Error::InvalidVertexObject(format!(
+                "attribute '{name}' holds {} values but was given {} values",
+                values.type_name(),
+                data.type_name()
+            )));
+        }
+        let components: usize = try_into!(self.component_size);
+        if !data.len().is_multiple_of(components) {
+            return Err(Error::InvalidVertexObject(format!(
+                "length of data ({}) must be evenly divisible by component size ({components})",
+                data.len()
+            )));
+        }
+        let len: usize = try_into!(len);
+        let count = data.len() / components;
+        if first + count > len {
+            return Err(Error::InvalidVertexObject(format!(
+                "updating {count} elements of attribute '{name}' from element {first} overflows its {len} elements"
+            )));
+        }
+        values.write_at(first * components, data);
+        Ok(first..first + count)
+    }
+
+    fn num_vertices(&self) -> GLint {
+        let num_values: GLint = self.data.as_ref().map_or(0, |d| try_into!(d.len()));
+        num_values / self.component_size
+    }
+
+    /// Only integer values can be normalized or passed to the shader as integers.
+    fn validate_format(&self) -> Result<()> {
+        let is_float = matches!(self.gl_type, gl::FLOAT | gl::HALF_FLOAT | gl::DOUBLE);
+        if is_float && self.format != AttributeFormat::Float {
+            return Err(Error::InvalidVertexObject(format!(
+                "attribute '{}' has floating point values so its format must be {:?}, not {:?}",
+                self.name,
+                AttributeFormat::Float,
+                self.format
+            )));
+        }
+        Ok(())
     }
 }
 
@@ -270,6 +902,26 @@ impl Drop for VertexObject {
     }
 }
 
+impl Drop for InstanceObject {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteBuffers(1, &self.gl_object_id);
+            self.gl_object_id = 0;
+        }
+    }
+}
+
+impl IndexObject {
+    /// Largest index, ignoring restarts.
+    pub(crate) fn max_index(&self) -> Option<u32> {
+        self.indexes
+            .iter()
+            .copied()
+            .filter(|index| !self.primitive_restart || *index != RESTART_INDEX)
+            .max()
+    }
+}
+
 impl Drop for IndexObject {
     fn drop(&mut self) {
         unsafe {
@@ -278,3 +930,266 @@ impl Drop for IndexObject {
         }
     }
 }
+
+/// Alignment of attributes whose values are `value_size` bytes: the size of their values, and at
+/// least 4 bytes as many drivers expect.
+fn attribute_alignment(value_size: usize) -> usize {
+    value_size.max(4)
+}
+
+/// Records the offset of each attribute within a vertex, aligned following
+/// [attribute_alignment]. Returns the size of a vertex in bytes.
+fn layout_attributes(attributes: &mut [VertexAttribute]) -> usize {
+    let mut offset: usize = 0;
+    let mut max_alignment = 4;
+    for attribute in attributes.iter_mut() {
+        let Some(data) = &attribute.data else {
+            continue;
+        };
+        let alignment = attribute_alignment(data.value_size());
+        let components: usize = try_into!(attribute.component_size);
+        attribute.offset = offset.next_multiple_of(alignment);
+        offset = attribute.offset + data.value_size() * components;
+        max_alignment = max_alignment.max(alignment);
+    }
+    offset.next_multiple_of(max_alignment)
+}
+
+/// Interleaves the data of every attribute for `vertices` into a single buffer following the
+/// offsets computed by [layout_attributes].
+fn interleave(attributes: &[VertexAttribute], stride: usize, vertices: Range<usize>) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(stride * vertices.len());
+    for vertex in vertices {
+        let start = buffer.len();
+        for attribute in attributes {
+            let Some(data) = &attribute.data else {
+                continue;
+            };
+            buffer.resize(start + attribute.offset, 0);
+            data.extend_vertex(vertex, attribute.component_size, &mut buffer);
+        }
+        buffer.resize(start + stride, 0);
+    }
+    buffer
+}
+
+/// Every index other than [RESTART_INDEX] must refer to a vertex.
+fn validate_indexes(indexes: &[u32], num_vertices: GLint) -> Result<()> {
+    let max_index = indexes
+        .iter()
+        .copied()
+        .filter(|index| *index != RESTART_INDEX)
+        .max()
+        .unwrap_or_default();
+    if max_index > try_into!(num_vertices - 1) {
+        return Err(Error::InvalidVertexObject(format!(
+            "index value {max_index} out of bounds with number of vertices, {num_vertices}"
+        )));
+    }
+    Ok(())
+}
+
+/// Points each of `attributes` at their values in the buffer bound to `GL_ARRAY_BUFFER`, for the
+/// bound vertex array.
+unsafe fn set_attribute_pointers(
+    ctx: &GLContext,
+    program: Handle<Program>,
+    attributes: &[VertexAttribute],
+    stride: GLint,
+) -> Result<()> {
+    for attribute in attributes {
+        let attr_loc = ctx.try_get_attrib_loc(program, &attribute.name)?;
+        let offset = attribute.offset as *const c_void;
+        match attribute.format {
+            AttributeFormat::Integer => gl::VertexAttribIPointer(
+                attr_loc,
+                attribute.component_size,
+                attribute.gl_type,
+                stride,
+                offset,
+            ),
+            format => gl::VertexAttribPointer(
+                attr_loc,
+                attribute.component_size,
+                attribute.gl_type,
+                as_gl_bool(format == AttributeFormat::Normalized),
+                stride,
+                offset,
+            ),
+        }
+        gl::EnableVertexAttribArray(attr_loc);
+        if attribute.divisor > 0 {
+            gl::VertexAttribDivisor(attr_loc, attribute.divisor);
+        }
+    }
+    Ok(())
+}
+
+/// Number of instances `len` values of an instance attribute with `divisor` cover.
+fn instances_covered(len: GLsizei, divisor: GLuint) -> GLsizei {
+    GLsizei::try_from(i64::from(len) * i64::from(divisor)).unwrap_or(GLsizei::MAX)
+}
+
+/// Re-uploads the elements in `range` of `attributes`, interleaved in `buffer`, from their
+/// CPU-side data.
+fn upload_range(
+    buffer: GLuint,
+    attributes: &[VertexAttribute],
+    stride: GLint,
+    range: Range<usize>,
+) {
+    let stride: usize = try_into!(stride);
+    let data = interleave(attributes, stride, range.clone());
+    unsafe {
+        gl::BindBuffer(gl::ARRAY_BUFFER, buffer);
+        gl::BufferSubData(
+            gl::ARRAY_BUFFER,
+            try_into!(range.start * stride),
+            try_into!(data.len()),
+            data.as_ptr() as *const c_void,
+        );
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(
+        name: &str,
+        components: GLint,
+        data: AttributeData,
+        format: AttributeFormat,
+    ) -> VertexAttribute {
+        VertexAttribute::new(name, components, data, format).unwrap()
+    }
+
+    #[test]
+    fn mixed_layout() {
+        let half = |v: f32| f16::from_f32(v);
+        let mut attributes = vec![
+            attribute(
+                "position",
+                3,
+                vec![
+                    half(1.0),
+                    half(2.0),
+                    half(3.0),
+                    half(4.0),
+                    half(5.0),
+                    half(6.0),
+                ]
+                .into(),
+                AttributeFormat::Float,
+            ),
+            attribute(
+                "color",
+                3,
+                vec![1u8, 2, 3, 4, 5, 6].into(),
+                AttributeFormat::Normalized,
+            ),
+            attribute("bone", 1, vec![7u32, 8].into(), AttributeFormat::Integer),
+        ];
+        let stride = layout_attributes(&mut attributes);
+        let offsets = attributes.iter().map(|a| a.offset).collect::<Vec<_>>();
+        assert_eq!(offsets, [0, 8, 12]);
+        assert_eq!(stride, 16);
+
+        let mut expected = Vec::new();
+        for vertex in 0..2 {
+            for value in 0..3 {
+                expected.extend(half((vertex * 3 + value + 1) as f32).to_ne_bytes());
+            }
+            expected.extend([0, 0]);
+            expected.extend([
+                vertex as u8 * 3 + 1,
+                vertex as u8 * 3 + 2,
+                vertex as u8 * 3 + 3,
+                0,
+            ]);
+            expected.extend((vertex as u32 + 7).to_ne_bytes());
+        }
+        assert_eq!(interleave(&attributes, stride, 0..2), expected);
+        assert_eq!(interleave(&attributes, stride, 1..2), expected[16..]);
+    }
+
+    #[test]
+    fn padding() {
+        let mut attributes = vec![attribute(
+            "uv",
+            2,
+            vec![1u8, 2, 3, 4].into(),
+            AttributeFormat::Normalized,
+        )];
+        assert_eq!(layout_attributes(&mut attributes), 4);
+        assert_eq!(interleave(&attributes, 4, 0..2), [1, 2, 0, 0, 3, 4, 0, 0]);
+
+        let mut attributes = vec![
+            attribute(
+                "position",
+                3,
+                vec![0.0f32; 3].into(),
+                AttributeFormat::Float,
+            ),
+            attribute("offset", 2, vec![-1i16, 1].into(), AttributeFormat::Float),
+        ];
+        assert_eq!(layout_attributes(&mut attributes), 16);
+        assert_eq!(attributes[1].offset, 12);
+    }
+
+    #[test]
+    fn format() {
+        let float = VertexAttribute::new("a", 1, vec![0.0f32].into(), AttributeFormat::Float);
+        assert!(float.is_ok());
+        for format in [AttributeFormat::Normalized, AttributeFormat::Integer] {
+            assert!(VertexAttribute::new("a", 1, vec![0.0f32].into(), format).is_err());
+            assert!(VertexAttribute::new("a", 1, vec![f16::ZERO].into(), format).is_err());
+            assert!(VertexAttribute::new("a", 1, vec![0u16].into(), format).is_ok());
+        }
+    }
+
+    #[test]
+    fn instances_covered_saturates() {
+        assert_eq!(instances_covered(10, 1), 10);
+        assert_eq!(instances_covered(10, 3), 30);
+        assert_eq!(instances_covered(0, GLuint::MAX), 0);
+        assert_eq!(instances_covered(2, GLuint::MAX), GLsizei::MAX);
+        assert_eq!(instances_covered(GLsizei::MAX, 2), GLsizei::MAX);
+    }
+
+    #[derive(Clone, Copy)]
+    #[repr(C)]
+    struct PackedVertex {
+        position: [f32; 3],
+        flag: u8,
+        color: [u8; 4],
+    }
+
+    unsafe impl Vertex for PackedVertex {
+        fn attributes() -> Vec<AttributeLayout> {
+            let layout = |name: &str, components, gl_type, offset| AttributeLayout {
+                name: name.to_string(),
+                components,
+                gl_type,
+                format: AttributeFormat::Float,
+                offset,
+            };
+            vec![
+                layout("position", 3, gl::FLOAT, 0),
+                layout("color", 4, gl::UNSIGNED_BYTE, mem::offset_of!(Self, color)),
+            ]
+        }
+    }
+
+    #[test]
+    fn unaligned_vertex_attributes() {
+        let vertices = [PackedVertex {
+            position: [0.0; 3],
+            flag: 0,
+            color: [0; 4],
+        }];
+        let builder = VOBuilder::new(Primitive::Points, Usage::Static);
+        assert!(builder.vertices(&vertices).is_err());
+    }
+}
@@ -1,3 +1,9 @@
+use crate::{
+    error::{Error, Result},
+    shader::ShaderType,
+};
+use gl::types::GLint;
+
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Primitive {
     Points,
@@ -9,6 +15,9 @@ pub enum Primitive {
     TriangleStrip,
     TriangleFan,
     Quads,
+    /// Input to tessellation shaders, with the given number of vertices per patch. Requires
+    /// OpenGL 4.0 and at most `GL_MAX_PATCH_VERTICES` vertices per patch.
+    Patches(gl::types::GLint),
 }
 
 impl From<Primitive> for gl::types::GLenum {
@@ -22,6 +31,33 @@ impl From<Primitive> for gl::types::GLenum {
             Primitive::TriangleStrip => gl::TRIANGLE_STRIP,
             Primitive::TriangleFan => gl::TRIANGLE_FAN,
             Primitive::Quads => gl::QUADS,
+            Primitive::Patches(_) => gl::PATCHES,
+        }
+    }
+}
+
+impl Primitive {
+    /// Checks that a context of `gl_version` can draw this primitive. Patches need tessellation
+    /// support and between 1 and `GL_MAX_PATCH_VERTICES` vertices per patch.
+    pub(crate) fn validate(self, gl_version: (GLint, GLint)) -> Result<()> {
+        let Primitive::Patches(vertices) = self else {
+            return Ok(());
+        };
+        let required = ShaderType::TessControl.min_gl_version();
+        if gl_version < required {
+            return Err(Error::UnsupportedGLVersion {
+                feature: "patch primitive",
+                required,
+                current: gl_version,
+            });
+        }
+        let mut max_vertices = 0;
+        unsafe { gl::GetIntegerv(gl::MAX_PATCH_VERTICES, &mut max_vertices) };
+        if !(1..=max_vertices).contains(&vertices) {
+            return Err(Error::InvalidVertexObject(format!(
+                "patches must have between 1 and {max_vertices} vertices but {vertices} were given"
+            )));
         }
+        Ok(())
     }
 }
@@ -0,0 +1,142 @@
+use super::RESTART_INDEX;
+use crate::error::{Error, Result};
+use gl::types::{GLint, GLsizei};
+
+/// A range of a [VertexObject](super::VertexObject) drawn on its own, e.g. the part of a model
+/// that uses a given material. With an index buffer, `first` and `count` refer to indexes and
+/// `base_vertex` is added to each index; otherwise they refer to vertices.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Submesh {
+    pub first: GLint,
+    pub count: GLsizei,
+    pub base_vertex: GLint,
+}
+
+impl Submesh {
+    pub fn new(first: GLint, count: GLsizei) -> Self {
+        Self {
+            first,
+            count,
+            base_vertex: 0,
+        }
+    }
+
+    /// Value added to every index of the submesh, so that ranges of the index buffer can refer
+    /// to their own vertices starting at 0.
+    pub fn base_vertex(mut self, base_vertex: GLint) -> Self {
+        self.base_vertex = base_vertex;
+        self
+    }
+
+    /// Checks that the submesh lies within `indexes`, or within the `num_vertices` vertices if
+    /// there are no indexes, and that the vertices it refers to exist.
+    pub(crate) fn validate(
+        &self,
+        name: &str,
+        num_vertices: usize,
+        indexes: Option<&[u32]>,
+        primitive_restart: bool,
+    ) -> Result<()> {
+        let (Ok(first), Ok(count), Ok(base_vertex)) = (
+            usize::try_from(self.first),
+            usize::try_from(self.count),
+            usize::try_from(self.base_vertex),
+        ) else {
+            return Err(Error::InvalidVertexObject(format!(
+                "range of submesh '{name}' can't be negative: {self:?}"
+            )));
+        };
+        let end = first + count;
+
+        let Some(indexes) = indexes else {
+            if base_vertex != 0 {
+                return Err(Error::InvalidVertexObject(format!(
+                    "submesh '{name}' has a base vertex but the vertex object has no indexes"
+                )));
+            }
+            if end > num_vertices {
+                return Err(Error::InvalidVertexObject(format!(
+                    "submesh '{name}' ends at vertex {end} but there are {num_vertices} vertices"
+                )));
+            }
+            return Ok(());
+        };
+        if end > indexes.len() {
+            return Err(Error::InvalidVertexObject(format!(
+                "submesh '{name}' ends at index {end} but there are {} indexes",
+                indexes.len()
+            )));
+        }
+        let max_vertex = indexes[first..end]
+            .iter()
+            .filter(|index| !primitive_restart || **index != RESTART_INDEX)
+            .max()
+            .map(|index| *index as usize + base_vertex);
+        if let Some(vertex) = max_vertex.filter(|vertex| *vertex >= num_vertices) {
+            return Err(Error::InvalidVertexObject(format!(
+                "submesh '{name}' refers to vertex {vertex} but there are {num_vertices} vertices"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEXES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+    #[test]
+    fn negative_fields() {
+        for submesh in [
+            Submesh::new(-1, 3),
+            Submesh::new(0, -3),
+            Submesh::new(0, 3).base_vertex(-1),
+        ] {
+            assert!(submesh.validate("s", 4, Some(&INDEXES), false).is_err());
+            assert!(submesh.validate("s", 4, None, false).is_err());
+        }
+    }
+
+    #[test]
+    fn base_vertex_without_indexes() {
+        let submesh = Submesh::new(0, 3).base_vertex(1);
+        assert!(submesh.validate("s", 8, None, false).is_err());
+        assert!(Submesh::new(0, 3).validate("s", 8, None, false).is_ok());
+    }
+
+    #[test]
+    fn vertex_range() {
+        assert!(Submesh::new(1, 3).validate("s", 4, None, false).is_ok());
+        assert!(Submesh::new(2, 3).validate("s", 4, None, false).is_err());
+    }
+
+    #[test]
+    fn index_range() {
+        assert!(Submesh::new(3, 3)
+            .validate("s", 4, Some(&INDEXES), false)
+            .is_ok());
+        assert!(Submesh::new(4, 3)
+            .validate("s", 4, Some(&INDEXES), false)
+            .is_err());
+    }
+
+    #[test]
+    fn referenced_vertices() {
+        let submesh = Submesh::new(3, 3).base_vertex(4);
+        assert!(submesh.validate("s", 8, Some(&INDEXES), false).is_ok());
+        assert!(submesh.validate("s", 7, Some(&INDEXES), false).is_err());
+        // Only the indexes within the range count.
+        let submesh = Submesh::new(0, 3).base_vertex(5);
+        assert!(submesh.validate("s", 8, Some(&INDEXES), false).is_ok());
+    }
+
+    #[test]
+    fn restart_indexes_are_skipped() {
+        let indexes = [0, 1, 2, RESTART_INDEX, 1, 2, 3];
+        let submesh = Submesh::new(0, 7);
+        assert!(submesh.validate("s", 4, Some(&indexes), true).is_ok());
+        assert!(submesh.validate("s", 4, Some(&indexes), false).is_err());
+    }
+}
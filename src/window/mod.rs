@@ -17,6 +17,8 @@ pub struct WindowConfig<'a> {
     pub initial_height: u32,
     pub title: &'a str,
     pub window_mode: WindowMode<'a>,
+    /// Bits per pixel of the default framebuffer's stencil buffer.
+    pub stencil_bits: u32,
 }
 
 impl Default for WindowConfig<'_> {
@@ -28,6 +30,7 @@ impl Default for WindowConfig<'_> {
             initial_height: 600,
             title: "",
             window_mode: WindowMode::Windowed,
+            stencil_bits: 8,
         }
     }
 }
@@ -41,6 +44,7 @@ impl Window {
             initial_width,
             title,
             window_mode,
+            stencil_bits,
         } = config;
 
         let mut glfw_obj = glfw::init_no_callbacks().map_err(Error::boxed)?;
@@ -49,6 +53,7 @@ impl Window {
             gl_major_version,
             gl_minor_version,
         ));
+        glfw_obj.window_hint(WindowHint::StencilBits(Some(stencil_bits)));
 
         #[cfg(target_os = "macos")]
         glfw_obj.window_hint(WindowHint::OpenGlForwardCompat(true));
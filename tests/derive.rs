@@ -0,0 +1,73 @@
+use gloam::{
+    uniform::{UniformType, Uniforms},
+    vertex::{AttributeFormat, AttributeLayout, Vertex},
+};
+use nalgebra_glm as glm;
+
+#[derive(Uniforms)]
+struct Camera {
+    #[uniform(name = "u_view")]
+    view: glm::Mat4,
+    #[uniform(transpose)]
+    projection: glm::Mat4,
+    exposure: f32,
+    weights: [f32; 3],
+    #[uniform(skip)]
+    _dirty: bool,
+}
+
+#[derive(Clone, Copy, Vertex)]
+#[repr(C)]
+struct ColoredVertex {
+    #[vertex(name = "aPos")]
+    position: glm::Vec3,
+    #[vertex(skip)]
+    _id: u32,
+    #[vertex(normalized)]
+    color: [u8; 4],
+}
+
+#[test]
+fn derive_uniforms() {
+    let camera = Camera {
+        view: glm::Mat4::identity(),
+        projection: glm::Mat4::identity(),
+        exposure: 1.5,
+        weights: [0.25, 0.5, 0.25],
+        _dirty: true,
+    };
+    let uniforms = camera.uniforms();
+
+    let names = uniforms.iter().map(|u| u.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["u_view", "projection", "exposure", "weights"]);
+
+    let transposed = uniforms.iter().map(|u| u.transpose).collect::<Vec<_>>();
+    assert_eq!(transposed, [false, true, false, false]);
+
+    assert!(matches!(uniforms[0].kind, UniformType::FMat4(_)));
+    assert!(matches!(uniforms[2].kind, UniformType::F(v) if v == 1.5));
+    assert!(matches!(&uniforms[3].kind, UniformType::FArray(v) if v == &[0.25, 0.5, 0.25]));
+}
+
+#[test]
+fn derive_vertex() {
+    assert_eq!(
+        ColoredVertex::attributes(),
+        [
+            AttributeLayout {
+                name: "aPos".to_string(),
+                components: 3,
+                gl_type: gl::FLOAT,
+                format: AttributeFormat::Float,
+                offset: 0,
+            },
+            AttributeLayout {
+                name: "color".to_string(),
+                components: 4,
+                gl_type: gl::UNSIGNED_BYTE,
+                format: AttributeFormat::Normalized,
+                offset: 16,
+            },
+        ]
+    );
+}
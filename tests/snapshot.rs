@@ -0,0 +1,70 @@
+#![cfg(feature = "headless")]
+
+use gloam::{
+    context::GLContext,
+    framebuffer::FramebufferBuilder,
+    headless::{Headless, HeadlessConfig},
+    object::GLObjectRegistry,
+    shader::{program::Linker, Shader, ShaderType},
+    snapshot::SnapshotTest,
+    texture::TextureBuilder,
+    vertex::{Primitive, Usage, VOBuilder},
+    Error, Result,
+};
+use std::path::PathBuf;
+
+#[test]
+fn hello_triangle() -> Result<()> {
+    let _headless = Headless::new(HeadlessConfig::default())?;
+    let mut ctx = GLContext::new(GLObjectRegistry::default())?;
+
+    let shader_dir = PathBuf::from("examples").join("hello_triangle");
+    let program = Linker::new()
+        .attach_shader(Shader::new(
+            shader_dir.join("hello_triangle_vertex.glsl"),
+            ShaderType::Vertex,
+        )?)
+        .attach_shader(Shader::new(
+            shader_dir.join("hello_triangle_fragment.glsl"),
+            ShaderType::Fragment,
+        )?)
+        .link(&mut ctx)?;
+
+    let triangle = VOBuilder::new(Primitive::Triangles, Usage::Static)
+        .attribute(
+            "aPosition",
+            3,
+            &[-0.5, -0.5, 0.0, 0.5, -0.5, 0.0, 0.0, 0.5, 0.0],
+        )?
+        .attribute("aColor", 3, &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0])?
+        .build(&mut ctx, program)?;
+
+    SnapshotTest::new("hello_triangle")
+        .size(128, 128)
+        .clear_color(0.2, 0.2, 0.2, 1.0)
+        .try_assert(&mut ctx, |ctx| {
+            ctx.try_use_program(program)?;
+            ctx.try_bind_vertex_object(triangle)?;
+            ctx.try_render()?;
+            ctx.unbind_current_vertex_object();
+            ctx.detach_current_program();
+            Ok(())
+        })
+}
+
+#[test]
+fn caller_framebuffer_stays_bound() -> Result<()> {
+    let _headless = Headless::new(HeadlessConfig::default())?;
+    let mut ctx = GLContext::new(GLObjectRegistry::default())?;
+
+    let framebuffer = FramebufferBuilder::new(16, 16)
+        .color_attachment(TextureBuilder::new_2d_rgba8_empty(16, 16))?
+        .build(&mut ctx)?;
+    ctx.try_bind_framebuffer(framebuffer)?;
+
+    let result =
+        SnapshotTest::new("caller_framebuffer_stays_bound").try_assert(&mut ctx, |_| Ok(()));
+    assert!(matches!(result, Err(Error::AnotherFramebufferBound)));
+    assert!(ctx.framebuffer_bound(framebuffer));
+    Ok(())
+}